@@ -0,0 +1,175 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+describe('uniqueness', () => {
+  test('unique compares elements with SameValueZero', () => {
+    const schema = b.array(b.number()).unique()
+    expect(schema.parse([1, 2])).toEqual([1, 2])
+    expect(schema.parseSafe([1, 2, 1])).toEqual({
+      success: false,
+      reason: 'Array contains duplicate elements (at 2)',
+    })
+    expect(schema.parseSafe([NaN, NaN]).success).toBe(false)
+  })
+
+  test('unique compares objects by reference', () => {
+    const schema = b.array(b.object({})).unique()
+    expect(schema.parseSafe([{}, {}]).success).toBe(true)
+  })
+
+  test('uniqueBy compares the value at a key path', () => {
+    const schema = b
+      .array(b.object({ user: b.object({ id: b.number() }) }))
+      .uniqueBy('user.id')
+    const users = [{ user: { id: 1 } }, { user: { id: 1 } }]
+    expect(schema.parseSafe(users)).toEqual({
+      success: false,
+      reason: "Array contains duplicate values for 'user.id' (at 1)",
+    })
+  })
+
+  test('compares the values written by async transforms', async () => {
+    const schema = b
+      .array(b.object({ id: b.string().transform(async s => s.toLowerCase()) }))
+      .uniqueBy('id')
+    expect(await schema.parseSafeAsync([{ id: 'A' }, { id: 'a' }])).toEqual({
+      success: false,
+      reason: "Array contains duplicate values for 'id' (at 1)",
+    })
+    expect(await schema.parseSafeAsync([{ id: 'A' }, { id: 'b' }])).toEqual({
+      success: true,
+      data: [{ id: 'a' }, { id: 'b' }],
+    })
+  })
+})
+
+describe('nonempty', () => {
+  test('requires at least one element', () => {
+    const schema = b.array(b.string()).nonempty()
+    expect(schema.parse(['a'])).toEqual(['a'])
+    expect(schema.parseSafe([]).success).toBe(false)
+  })
+})
+
+describe('contains', () => {
+  const schema = b.array(b.number())
+  const large = b.number().min(5)
+
+  test('requires one matching element by default', () => {
+    expect(schema.contains(large).parseSafe([1, 5]).success).toBe(true)
+    expect(schema.contains(large).parseSafe([1])).toEqual({
+      success: false,
+      reason: 'Array contains 0 matching element(s), expected at least 1',
+    })
+  })
+
+  test('min and max bound the matching elements', () => {
+    const bounded = schema.contains(large, { min: 1, max: 1 })
+    expect(bounded.parseSafe([5, 1]).success).toBe(true)
+    expect(bounded.parseSafe([5, 6])).toEqual({
+      success: false,
+      reason: 'Array contains 2 matching element(s), expected at most 1',
+    })
+  })
+
+  test('awaits async steps of the contains type', async () => {
+    const schema = b
+      .array(b.number())
+      .contains(b.number().refine(async value => value > 2))
+    expect(await schema.parseSafeAsync([1, 3])).toEqual({
+      success: true,
+      data: [1, 3],
+    })
+    expect(await schema.parseSafeAsync([1, 2])).toEqual({
+      success: false,
+      reason: 'Array contains 0 matching element(s), expected at least 1',
+    })
+  })
+
+  test('does not write the values parsed by the contains type', () => {
+    const schema = b
+      .array(b.object({ v: b.string() }))
+      .contains(b.object({ v: b.string().transform(s => s + '!') }))
+    const input = [{ v: 'x' }]
+    expect(schema.parse(input)).toEqual([{ v: 'x' }])
+    expect(input).toEqual([{ v: 'x' }])
+  })
+})
+
+describe('array detection', () => {
+  const sparse = () => {
+    const array = ['a', 'b', 'c']
+    delete array[1]
+    return array
+  }
+
+  test('rejects objects with a length', () => {
+    expect(b.array(b.number()).parseSafe({ length: 1, 0: 1 })).toEqual({
+      success: false,
+      reason: 'expect Array, got: Object',
+    })
+  })
+
+  test('holes are parsed as undefined by default', () => {
+    expect(b.array(b.string()).parseSafe(sparse())).toEqual({
+      success: false,
+      reason: 'Got undefined for required value (at 1)',
+    })
+    expect(b.array(b.string().optional()).parseSafe(sparse()).success).toBe(
+      true
+    )
+  })
+
+  test('holes can be rejected', () => {
+    const schema = b.array(b.string().optional()).holes('reject')
+    expect(schema.parseSafe(sparse())).toEqual({
+      success: false,
+      reason: 'Array has a hole (at 1)',
+    })
+  })
+
+  test('holes can be compacted', () => {
+    const schema = b.array(b.string()).holes('compact')
+    expect(schema.parse(sparse())).toEqual(['a', 'c'])
+  })
+
+  test('tuples use the hole policy as well', () => {
+    const schema = b.tuple(b.string(), b.string().optional()).holes('reject')
+    expect(schema.parseSafe(sparse().slice(0, 2)).success).toBe(false)
+  })
+
+  test('arrayLike converts arguments and typed arrays', () => {
+    const schema = b.array(b.number()).arrayLike()
+    const args = Reflect.apply(
+      function () {
+        return arguments
+      },
+      undefined,
+      [1, 2]
+    )
+    expect(schema.parse(args)).toEqual([1, 2])
+    expect(schema.parse(new Uint8Array([1, 2]))).toEqual([1, 2])
+    expect(schema.parseSafe(new Set([1])).success).toBe(false)
+    expect(b.array(b.number()).parseSafe(args).success).toBe(false)
+  })
+})
+
+describe('element errors', () => {
+  test('include the index of the element', () => {
+    const schema = b.array(b.number().max(3))
+    expect(schema.parseSafe([1, 2, 5])).toEqual({
+      success: false,
+      reason: 'Number is higher than maximum (at 2)',
+    })
+  })
+
+  test('include the keys of enclosing objects', () => {
+    const schema = b.object({
+      users: b.array(b.object({ name: b.string() })),
+    })
+    expect(schema.parseSafe({ users: [{ name: 'a' }, { name: 1 }] })).toEqual({
+      success: false,
+      reason: 'expect String, got: Number (at users.1.name)',
+    })
+  })
+})
@@ -0,0 +1,128 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+describe('parseAsync', () => {
+  test('awaits async transforms and refinements', async () => {
+    const schema = b.object({
+      name: b.string().transform(async value => value.toUpperCase()),
+      age: b.number().refine(async value => value >= 18, 'Too young'),
+    })
+    expect(await schema.parseAsync({ name: 'ada', age: 36 })).toEqual({
+      name: 'ADA',
+      age: 36,
+    })
+    await expect(schema.parseAsync({ name: 'ada', age: 3 })).rejects.toThrow(
+      'Too young'
+    )
+  })
+
+  test('parse() fails for async steps', () => {
+    const schema = b.string().refine(async () => true)
+    expect(() => schema.parse('a')).toThrow('use parseAsync() instead')
+  })
+
+  test('rejections of nested async steps include the path', async () => {
+    const schema = b.object({
+      users: b.array(
+        b.object({ name: b.string().refine(async () => false, 'Taken') })
+      ),
+    })
+    await expect(
+      schema.parseAsync({ users: [{ name: 'a' }] })
+    ).rejects.toThrow('Taken (at users.0.name)')
+  })
+
+  test('rejections of steps after an async transform include the path', async () => {
+    const schema = b.object({
+      id: b
+        .string()
+        .transform(async value => Number(value))
+        .refine(value => value > 0, 'Not positive'),
+    })
+    await expect(schema.parseAsync({ id: '-1' })).rejects.toThrow(
+      'Not positive (at id)'
+    )
+  })
+
+  test('rejections keep messages which look like a path', async () => {
+    const schema = b.object({
+      x: b.array(b.number().refine(async () => false, 'bad (at home)')),
+    })
+    await expect(schema.parseAsync({ x: [1] })).rejects.toThrow(
+      /^bad \(at home\) \(at x\.0\)$/
+    )
+    expect(await schema.parseSafeAsync({ x: [1] })).toEqual({
+      success: false,
+      reason: 'bad (at home) (at x.0)',
+    })
+  })
+
+  test('errors thrown by callbacks are rejected unchanged', async () => {
+    const schema = b.object({
+      a: b.string().refine(async () => {
+        throw new Error('boom')
+      }),
+    })
+    await expect(schema.parseAsync({ a: 'x' })).rejects.toThrow(/^boom$/)
+  })
+})
+
+describe('parseSafeAsync', () => {
+  test('includes warnings added after an async step', async () => {
+    const schema = b.object({
+      old: b
+        .string()
+        .transform(async value => value)
+        .pipe(b.string().deprecated('Use new')),
+    })
+    expect(await schema.parseSafeAsync({ old: 'a' })).toEqual({
+      success: true,
+      data: { old: 'a' },
+      warnings: ['Value is deprecated: Use new (at old)'],
+    })
+  })
+})
+
+describe('async unions', () => {
+  test('a member only matches once its async steps passed', async () => {
+    const schema = b.union(
+      b.string().refine(async () => false, 'bad'),
+      b.string()
+    )
+    expect(await schema.parseAsync('x')).toBe('x')
+  })
+
+  test('rejects when no member passes', async () => {
+    const schema = b.union(
+      b.string().refine(async () => false, 'bad'),
+      b.number()
+    )
+    await expect(schema.parseAsync('x')).rejects.toThrow(
+      'Value did not match any of the Union types'
+    )
+  })
+
+  test('warnings of members which did not match are dropped', async () => {
+    const schema = b.union(
+      b.object({
+        mode: b.string().deprecated(),
+        id: b.string().refine(async () => false),
+      }),
+      b.object({ mode: b.string() })
+    )
+    expect(await schema.parseSafeAsync({ mode: 'a', id: 'b' })).toEqual({
+      success: true,
+      data: { mode: 'a' },
+    })
+  })
+
+  test('errors thrown by callbacks are not treated as a mismatch', async () => {
+    const schema = b.union(
+      b.string().refine(async () => {
+        throw new Error('boom')
+      }),
+      b.string()
+    )
+    await expect(schema.parseAsync('x')).rejects.toThrow('boom')
+  })
+})
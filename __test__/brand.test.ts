@@ -0,0 +1,35 @@
+import { describe, expect, test } from 'bun:test'
+
+import b from '../index'
+
+describe('brand', () => {
+  test('accepts values of the branded schema unchanged', () => {
+    const userId = b.string().brand('UserId')
+    expect(userId.parse('u1')).toBe('u1')
+  })
+
+  test('names the brand in errors', () => {
+    const userId = b.string().brand('UserId')
+    expect(userId.parseSafe(1)).toEqual({
+      success: false,
+      reason: 'Expected UserId: expect String, got: Number',
+    })
+  })
+
+  test('keeps the path of branded properties', () => {
+    const schema = b.object({ id: b.number().brand('OrderId') })
+    expect(schema.parseSafe({ id: 'x' })).toEqual({
+      success: false,
+      reason: 'Expected OrderId: expect Number, got: String (at id)',
+    })
+  })
+
+  test('is carried in the definition', () => {
+    expect(b.string().brand('UserId').toDefinition()).toEqual({
+      type: 'string',
+      optional: false,
+      nullable: false,
+      brand: 'UserId',
+    })
+  })
+})
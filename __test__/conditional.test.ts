@@ -0,0 +1,42 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+describe('conditional', () => {
+  const schema = b.conditional(
+    b.object({ type: b.enum(['card']) }),
+    b.object({ type: b.string(), number: b.string() }),
+    b.object({ type: b.string(), iban: b.string() })
+  )
+
+  test('parses with the then schema when the if schema matches', () => {
+    expect(schema.parse({ type: 'card', number: '4242' })).toEqual({
+      type: 'card',
+      number: '4242',
+    })
+    expect(schema.parseSafe({ type: 'card', iban: 'DE00' })).toEqual({
+      success: false,
+      reason: "Object missing required key 'number'",
+    })
+  })
+
+  test('parses with the else schema otherwise', () => {
+    expect(schema.parse({ type: 'bank', iban: 'DE00' })).toEqual({
+      type: 'bank',
+      iban: 'DE00',
+    })
+  })
+
+  test('values not matching the if schema pass without an else schema', () => {
+    const strings = b.conditional(b.string(), b.string().min(2))
+    expect(strings.parse(1)).toBe(1)
+    expect(strings.parseSafe('a').success).toBe(false)
+  })
+
+  test('the if schema does not change the value', () => {
+    const trimmed = b.conditional(
+      b.string().transform(value => value.trim()),
+      b.string()
+    )
+    expect(trimmed.parse(' a ')).toBe(' a ')
+  })
+})
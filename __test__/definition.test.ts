@@ -0,0 +1,140 @@
+import { describe, expect, test } from 'bun:test'
+
+import b from '../index'
+
+describe('toDefinition', () => {
+  test('describes constraints and modifiers', () => {
+    expect(b.number().int().min(0).optional().toDefinition()).toEqual({
+      type: 'number',
+      optional: true,
+      nullable: false,
+      min: 0,
+      int: true,
+    })
+    expect(b.string().min(1).max(5).pattern('^a').toDefinition()).toEqual({
+      type: 'string',
+      optional: false,
+      nullable: false,
+      min: 1,
+      max: 5,
+      pattern: '^a',
+    })
+  })
+
+  test('describes children of containers', () => {
+    const schema = b
+      .object({
+        tags: b.array(b.enum(['x', 'y'])).unique(),
+        pair: b.tuple.named(['a', b.boolean()], ['b', b.Null()]),
+        u: b.union(b.string(), b.number()).nullable(),
+      })
+      .strict()
+    expect(schema.toDefinition()).toEqual({
+      type: 'object',
+      optional: false,
+      nullable: false,
+      strict: true,
+      shape: {
+        tags: {
+          type: 'array',
+          optional: false,
+          nullable: false,
+          unique: true,
+          element: {
+            type: 'enum',
+            optional: false,
+            nullable: false,
+            values: ['x', 'y'],
+          },
+        },
+        pair: {
+          type: 'tuple',
+          optional: false,
+          nullable: false,
+          names: ['a', 'b'],
+          elements: [
+            { type: 'boolean', optional: false, nullable: false },
+            { type: 'null', optional: false, nullable: true },
+          ],
+        },
+        u: {
+          type: 'union',
+          optional: false,
+          nullable: true,
+          options: [
+            { type: 'string', optional: false, nullable: false },
+            { type: 'number', optional: false, nullable: false },
+          ],
+        },
+      },
+    })
+  })
+
+  test('is JSON serializable', () => {
+    const definition = b
+      .object({ a: b.array(b.string().describe('A')) })
+      .toDefinition()
+    expect(JSON.parse(JSON.stringify(definition))).toEqual(definition)
+  })
+})
+
+describe('fromDefinition', () => {
+  const schema = b
+    .object({
+      name: b.string().min(1).max(5).pattern('^a'),
+      age: b.number().int().min(0).optional(),
+      tags: b.array(b.enum(['x', 'y'])).unique(),
+      pair: b.tuple.named(['a', b.boolean()], ['b', b.Null()]),
+      u: b.union(b.string(), b.number()).nullable(),
+    })
+    .strict()
+
+  test('round trips through toDefinition', () => {
+    const definition = schema.toDefinition()
+    expect(b.fromDefinition(definition).toDefinition()).toEqual(definition)
+  })
+
+  test('rebuilds a schema that parses like the original', () => {
+    const rebuilt = b.fromDefinition(
+      JSON.parse(JSON.stringify(schema.toDefinition())),
+    )
+    const valid = { name: 'ab', tags: ['x'], pair: [true, null], u: null }
+    const invalid = [
+      { ...valid, name: 'b' },
+      { ...valid, age: 1.5 },
+      { ...valid, tags: ['x', 'x'] },
+      { ...valid, extra: 1 },
+    ]
+    expect(rebuilt.parseSafe(valid)).toEqual(schema.parseSafe(valid))
+    for (const value of invalid) {
+      expect(rebuilt.parseSafe(value)).toEqual(schema.parseSafe(value))
+    }
+  })
+
+  test('rejects unknown types', () => {
+    expect(() => b.fromDefinition({ type: 'nope' })).toThrow(
+      "Invalid definition: Unknown type 'nope' (at type)",
+    )
+    expect(() =>
+      b.fromDefinition({ type: 'object', shape: { a: { type: 'wat' } } }),
+    ).toThrow("Invalid definition: Unknown type 'wat' (at shape.a.type)")
+  })
+
+  test('rejects inconsistent constraints', () => {
+    expect(() => b.fromDefinition({ type: 'string', min: 3, max: 1 })).toThrow(
+      "Invalid definition: 'min' (3) is higher than 'max' (1)",
+    )
+  })
+
+  test('rejects invalid patterns', () => {
+    expect(() => b.fromDefinition({ type: 'string', pattern: '(' })).toThrow(
+      "Invalid definition: Invalid regex '('",
+    )
+  })
+
+  test('rejects missing children', () => {
+    expect(() => b.fromDefinition({ type: 'array' })).toThrow(
+      'Invalid definition: Got undefined for required value (at element)',
+    )
+  })
+})
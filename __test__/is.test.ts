@@ -0,0 +1,221 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+class Point {
+  x = 1
+}
+
+const sparse = () => {
+  const array = ['a', 'b']
+  delete array[0]
+  return array
+}
+
+// values are created for every call, as parsing can change the input
+const cases = [
+  ['string', () => b.string().min(2).max(4), () => ['ab', 'abcde', 'a', 1]],
+  ['string length', () => b.string().length(2), () => ['ab', 'abc']],
+  ['string pattern', () => b.string().pattern('^a+$'), () => ['aa', 'ab']],
+  ['string format', () => b.string().format('email'), () => ['a@b.co', 'a@']],
+  [
+    'number',
+    () => b.number().int().min(0).max(10),
+    () => [1, 1.5, -1, 11, '1'],
+  ],
+  ['number width', () => b.number().width('uint8'), () => [255, 256, -1]],
+  ['boolean', () => b.boolean().isTrue(), () => [true, false, 0]],
+  ['enum', () => b.enum(['a', 'b']), () => ['a', 'c']],
+  ['optional', () => b.string().optional(), () => [undefined, null, 'a']],
+  ['nullable', () => b.string().nullable(), () => [undefined, null, 'a']],
+  [
+    'array',
+    () => b.array(b.number()).min(1).max(2),
+    () => [[1], [], [1, 2, 3], ['a']],
+  ],
+  ['array unique', () => b.array(b.number()).unique(), () => [[1, 2], [1, 1]]],
+  [
+    'array uniqueBy',
+    () => b.array(b.object({ id: b.number() })).uniqueBy('id'),
+    () => [
+      [{ id: 1 }, { id: 2 }],
+      [{ id: 1 }, { id: 1 }],
+    ],
+  ],
+  [
+    'array contains',
+    () => b.array(b.number()).contains(b.number().min(5), { min: 2 }),
+    () => [
+      [5, 6],
+      [5, 1],
+    ],
+  ],
+  [
+    'array holes',
+    () => b.array(b.string()),
+    () => [sparse(), ['a']],
+  ],
+  [
+    'array holes reject',
+    () => b.array(b.string()).holes('reject'),
+    () => [sparse(), ['a']],
+  ],
+  [
+    'array holes compact',
+    () => b.array(b.string()).holes('compact').length(1),
+    () => [sparse(), ['a', 'b']],
+  ],
+  [
+    'array arrayLike',
+    () => b.array(b.number()).arrayLike(),
+    () => [{ length: 1, 0: 1 }, { length: 1, 0: 'a' }, new Set([1])],
+  ],
+  [
+    'tuple',
+    () => b.tuple(b.string(), b.number()),
+    () => [['a', 1], ['a'], ['a', 1, 2]],
+  ],
+  [
+    'tuple rest',
+    () => b.tuple(b.string()).rest(b.number()),
+    () => [['a'], ['a', 1, 2], ['a', 'b']],
+  ],
+  [
+    'tuple optional tail',
+    () => b.tuple(b.string(), b.number().optional()),
+    () => [['a'], ['a', 1], ['a', 'b']],
+  ],
+  [
+    'object',
+    () => b.object({ a: b.string() }),
+    () => [{ a: 'a' }, { a: 'a', b: 1 }, {}, 1],
+  ],
+  [
+    'object strict',
+    () => b.object({ a: b.string() }).strict(),
+    () => [{ a: 'a' }, { a: 'a', b: 1 }],
+  ],
+  [
+    'object catchall',
+    () => b.object({ a: b.string() }).catchall(b.number()),
+    () => [
+      { a: 'a', b: 1 },
+      { a: 'a', b: 'b' },
+    ],
+  ],
+  [
+    'object patternProperties',
+    () => b.object({}).patternProperties({ '^n_': b.number() }).strict(),
+    () => [{ n_a: 1 }, { n_a: 'a' }, { x: 1 }],
+  ],
+  [
+    'object propertyNames',
+    () => b.object({}).catchall(b.number()).propertyNames(b.string().max(2)),
+    () => [{ ab: 1 }, { abc: 1 }],
+  ],
+  [
+    'object property count',
+    () => b.object({}).catchall(b.number()).minProperties(1).maxProperties(2),
+    () => [{}, { a: 1 }, { a: 1, b: 2, c: 3 }],
+  ],
+  [
+    'object property count without removed keys',
+    () => b.object({ a: b.number() }).maxProperties(1),
+    () => [{ a: 1, b: 2 }],
+  ],
+  [
+    'object plain',
+    () => b.object({}).plain(),
+    () => [{}, Object.create(null), new Point(), new Date(), []],
+  ],
+  ['object rejectArrays', () => b.object({}).rejectArrays(), () => [{}, []]],
+  [
+    'object key alias',
+    () => b.object({}).key('userId', b.number(), { from: 'user_id' }),
+    () => [
+      { user_id: 1 },
+      { userId: 1 },
+      { user_id: 'a' },
+      { user_id: 1, userId: 2 },
+    ],
+  ],
+  [
+    'object camelCaseKeys',
+    () => b.object({ userId: b.number() }).camelCaseKeys('snake_case'),
+    () => [
+      { user_id: 1 },
+      { userId: 1 },
+      { user_id: 'a' },
+      { user_id: 1, userId: 1 },
+    ],
+  ],
+  [
+    'object rules',
+    () =>
+      b
+        .object({ a: b.number().optional(), b: b.number().optional() })
+        .lessThan('a', 'b')
+        .atLeastOneOf(['a', 'b']),
+    () => [{ a: 1, b: 2 }, { a: 2, b: 1 }, {}],
+  ],
+  [
+    'object partial and pick',
+    () => b.object({ a: b.string(), b: b.number() }).partial(['a']).pick(['a']),
+    () => [{}, { a: 1 }],
+  ],
+  ['instanceof', () => b.instanceof(Point), () => [new Point(), { x: 1 }]],
+  ['union', () => b.union(b.string(), b.number()), () => ['a', 1, true]],
+  [
+    'union exclusive',
+    () => b.union(b.string(), b.string().min(2)).exclusive(),
+    () => ['a', 'ab'],
+  ],
+  [
+    'union of objects renaming keys',
+    () =>
+      b.union(
+        b.object({ userId: b.number() }).camelCaseKeys('snake_case'),
+        b.object({ user_id: b.string() })
+      ),
+    () => [{ user_id: 'x' }, { user_id: 1 }, { user_id: true }],
+  ],
+  [
+    'conditional',
+    () => b.conditional(b.string(), b.string().min(2), b.number()),
+    () => ['ab', 'a', 1, true],
+  ],
+  ['refine', () => b.string().refine(value => value !== 'x'), () => ['a', 'x']],
+  [
+    'superRefine',
+    () =>
+      b.string().superRefine((value, ctx) => {
+        if (value === 'x') ctx.addIssue({ message: 'x' })
+      }),
+    () => ['a', 'x'],
+  ],
+  [
+    'transform and pipe',
+    () => b.string().transform(Number).pipe(b.number().int()),
+    () => ['1', '1.5'],
+  ],
+  [
+    'preprocess',
+    () => b.preprocess(value => value ?? 'a', b.string()),
+    () => [undefined, 1],
+  ],
+  ['brand', () => b.string().min(2).brand('Name'), () => ['ab', 'a']],
+  [
+    'readonly',
+    () => b.object({ a: b.string() }).readonly(true),
+    () => [{ a: 'a' }, { a: 1 }],
+  ],
+  ['deprecated', () => b.string().deprecated(), () => ['a', 1]],
+]
+
+describe('is() agrees with parseSafe()', () => {
+  for (const [name, schema, values] of cases) {
+    test(name, () => {
+      const parsed = values().map(value => schema().parseSafe(value).success)
+      expect(values().map(value => schema().is(value))).toEqual(parsed)
+    })
+  }
+})
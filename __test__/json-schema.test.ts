@@ -0,0 +1,222 @@
+import { describe, expect, test } from 'bun:test'
+
+import b from '../index'
+
+const draft = 'https://json-schema.org/draft/2020-12/schema'
+
+describe('toJSONSchema', () => {
+  test('maps string lengths', () => {
+    expect(b.string().min(2).max(3).toJSONSchema()).toEqual({
+      $schema: draft,
+      type: 'string',
+      minLength: 2,
+      maxLength: 3,
+    })
+  })
+
+  test('counts string lengths in code points like JSON Schema', () => {
+    const schema = b.string().min(2).max(3)
+    expect(schema.parseSafe('héé').success).toBe(true)
+    expect(schema.parseSafe('😀😀').success).toBe(true)
+    expect(schema.parseSafe('éééé').success).toBe(false)
+    expect(schema.parseSafe('😀').success).toBe(false)
+    expect(schema.is('日本')).toBe(true)
+    expect(b.string().length(2).parseSafe('日本')).toEqual({
+      success: true,
+      data: '日本',
+    })
+  })
+
+  test('maps numbers, tuples, unions and nullable values', () => {
+    const schema = b.object({
+      n: b.number().int().min(1).max(9).nullable(),
+      t: b.tuple(b.string(), b.boolean()),
+      u: b.union(b.string(), b.number()),
+      o: b.string().optional(),
+    })
+    expect(schema.toJSONSchema()).toEqual({
+      $schema: draft,
+      type: 'object',
+      properties: {
+        n: { type: ['integer', 'null'], minimum: 1, maximum: 9 },
+        t: {
+          type: 'array',
+          prefixItems: [{ type: 'string' }, { type: 'boolean' }],
+          items: false,
+          minItems: 2,
+        },
+        u: { anyOf: [{ type: 'string' }, { type: 'number' }] },
+        o: { type: 'string' },
+      },
+      required: ['n', 't', 'u'],
+    })
+  })
+
+  test('disallows additional properties of strict objects', () => {
+    const schema = b.object({ a: b.string() }).strict()
+    expect(schema.toJSONSchema()).toMatchObject({
+      additionalProperties: false,
+    })
+  })
+
+  test('moves shared subtrees to $defs', () => {
+    const point = b.object({ x: b.number() })
+    expect(b.object({ a: point, c: point }).toJSONSchema()).toEqual({
+      $schema: draft,
+      type: 'object',
+      properties: {
+        a: { $ref: '#/$defs/Schema1' },
+        c: { $ref: '#/$defs/Schema1' },
+      },
+      required: ['a', 'c'],
+      $defs: {
+        Schema1: {
+          type: 'object',
+          properties: { x: { type: 'number' } },
+          required: ['x'],
+        },
+      },
+    })
+  })
+
+  test('rejects unknown drafts', () => {
+    // @ts-expect-error the draft is not one of the supported ones
+    expect(() => b.string().toJSONSchema({ draft: '2019-09' })).toThrow(
+      "Unknown JSON Schema draft '2019-09'",
+    )
+  })
+})
+
+describe('fromJSONSchema', () => {
+  test('counts string lengths in code points', () => {
+    const schema = b.fromJSONSchema({
+      type: 'string',
+      minLength: 2,
+      maxLength: 3,
+    })
+    expect(schema.parseSafe('日本').success).toBe(true)
+    expect(schema.parseSafe('héé').success).toBe(true)
+    expect(schema.parseSafe('😀').success).toBe(false)
+    expect(schema.parseSafe('éééé').success).toBe(false)
+  })
+
+  test('round trips non-ASCII string lengths', () => {
+    const schema = b.string().min(2).max(3)
+    const imported = b.fromJSONSchema(schema.toJSONSchema())
+    for (const value of ['日本', 'héé', '😀', 'éééé']) {
+      expect(imported.parseSafe(value)).toEqual(schema.parseSafe(value))
+    }
+  })
+
+  test('keeps unknown keys when additionalProperties is absent', () => {
+    const schema = b.fromJSONSchema({
+      type: 'object',
+      properties: { a: { type: 'string' } },
+      required: ['a'],
+    })
+    expect(schema.parse({ a: 'x', extra: 1 })).toEqual({ a: 'x', extra: 1 })
+    expect(schema.is({ a: 'x', extra: 1 })).toBe(true)
+  })
+
+  test('keeps unknown keys when additionalProperties is true', () => {
+    const schema = b.fromJSONSchema({
+      type: 'object',
+      properties: {},
+      additionalProperties: true,
+    })
+    expect(schema.parse({ extra: 1 })).toEqual({ extra: 1 })
+  })
+
+  test('rejects unknown keys when additionalProperties is false', () => {
+    const schema = b.fromJSONSchema({
+      type: 'object',
+      properties: {},
+      additionalProperties: false,
+    })
+    expect(schema.parseSafe({ extra: 1 })).toEqual({
+      success: false,
+      reason: 'Unknown key(s) [extra] in strict object',
+    })
+  })
+
+  test('validates unknown keys against an additionalProperties schema', () => {
+    const schema = b.fromJSONSchema({
+      type: 'object',
+      properties: {},
+      additionalProperties: { type: 'number' },
+    })
+    expect(schema.parse({ extra: 1 })).toEqual({ extra: 1 })
+    expect(schema.parseSafe({ extra: 'x' })).toEqual({
+      success: false,
+      reason: 'expect Number, got: String (at extra)',
+    })
+  })
+
+  test('keeps keys not matching patternProperties', () => {
+    const schema = b.fromJSONSchema({
+      type: 'object',
+      patternProperties: { '^n': { type: 'number' } },
+    })
+    expect(schema.parse({ n: 1, s: 'x' })).toEqual({ n: 1, s: 'x' })
+    expect(schema.parseSafe({ n: 'x' })).toEqual({
+      success: false,
+      reason: 'expect Number, got: String (at n)',
+    })
+  })
+
+  test('round trips objects', () => {
+    const schema = b.object({ a: b.string(), b: b.number().optional() })
+    const document = schema.toJSONSchema()
+    expect(b.fromJSONSchema(document).toJSONSchema()).toEqual(document)
+  })
+
+  test('accepts $refs reached through several allOf members', () => {
+    const schema = b.fromJSONSchema({
+      allOf: [{ $ref: '#/$defs/A' }, { $ref: '#/$defs/B' }],
+      $defs: {
+        A: { type: 'object', properties: { a: { type: 'string' } } },
+        B: {
+          allOf: [{ $ref: '#/$defs/A' }],
+          properties: { b: { type: 'number' } },
+        },
+      },
+    })
+    expect(schema.parse({ a: 'x', b: 1 })).toEqual({ a: 'x', b: 1 })
+    expect(schema.parseSafe({ a: 1 })).toEqual({
+      success: false,
+      reason: 'expect String, got: Number (at a)',
+    })
+  })
+
+  test('rejects recursive $refs', () => {
+    expect(() =>
+      b.fromJSONSchema({
+        $ref: '#/$defs/A',
+        $defs: {
+          A: { type: 'object', properties: { next: { $ref: '#/$defs/A' } } },
+        },
+      }),
+    ).toThrow("Recursive $ref '#/$defs/A' is not supported")
+  })
+
+  test('keeps only enum values of the declared type', () => {
+    const strings = b.fromJSONSchema({ type: 'string', enum: ['a', 1] })
+    expect(strings.is('a')).toBe(true)
+    expect(strings.is(1)).toBe(false)
+
+    const integers = b.fromJSONSchema({
+      type: ['integer', 'null'],
+      enum: ['a', 1, 1.5, null],
+    })
+    expect(integers.is(1)).toBe(true)
+    expect(integers.is(null)).toBe(true)
+    expect(integers.is(1.5)).toBe(false)
+    expect(integers.is('a')).toBe(false)
+  })
+
+  test('rejects enums without values of the declared type', () => {
+    expect(() => b.fromJSONSchema({ type: 'boolean', enum: ['a'] })).toThrow(
+      "No values match 'type' (at enum)",
+    )
+  })
+})
@@ -0,0 +1,200 @@
+import { describe, expect, test } from 'bun:test'
+
+import b from '../index'
+
+describe('number widths', () => {
+  test('restrict integers to their range', () => {
+    const uint8 = b.number().width('uint8')
+    expect(uint8.parseSafe(0)).toEqual({ success: true, data: 0 })
+    expect(uint8.parseSafe(255)).toEqual({ success: true, data: 255 })
+    for (const value of [256, -1, 1.5]) {
+      expect(uint8.parseSafe(value)).toEqual({
+        success: false,
+        reason: 'Number is not a valid uint8',
+      })
+      expect(uint8.is(value)).toBe(false)
+    }
+  })
+
+  test('include both ends of signed ranges', () => {
+    const int32 = b.number().width('int32')
+    expect(int32.parseSafe(-(2 ** 31)).success).toBe(true)
+    expect(int32.parseSafe(2 ** 31 - 1).success).toBe(true)
+    expect(int32.parseSafe(2 ** 31).success).toBe(false)
+  })
+
+  test('reject numbers out of the float32 range', () => {
+    const float32 = b.number().width('float32')
+    expect(float32.parseSafe(1.5).success).toBe(true)
+    expect(float32.parseSafe(1e40)).toEqual({
+      success: false,
+      reason: 'Number is not a valid float32',
+    })
+  })
+
+  test('are exported as JTD types', () => {
+    expect(b.number().width('uint8').toJTD()).toEqual({ type: 'uint8' })
+    expect(b.number().toJTD()).toEqual({ type: 'float64' })
+  })
+})
+
+describe('toJTD and fromJTD', () => {
+  test('round trip objects', () => {
+    const schema = b.object({
+      id: b.number().width('uint32'),
+      name: b.string(),
+      tags: b.array(b.string()),
+      scores: b.object({}).catchall(b.number()),
+      level: b.enum(['a', 'b']),
+      note: b.string().optional(),
+      nick: b.string().nullable(),
+      at: b.string().format('date-time'),
+      flag: b.boolean(),
+    })
+    const jtd = schema.toJTD()
+    expect(jtd).toEqual({
+      properties: {
+        at: { type: 'timestamp' },
+        flag: { type: 'boolean' },
+        id: { type: 'uint32' },
+        level: { enum: ['a', 'b'] },
+        name: { type: 'string' },
+        nick: { type: 'string', nullable: true },
+        scores: { values: { type: 'float64' } },
+        tags: { elements: { type: 'string' } },
+      },
+      optionalProperties: { note: { type: 'string' } },
+      additionalProperties: true,
+    })
+    expect(b.fromJTD(jtd).toJTD()).toEqual(jtd)
+  })
+
+  test('round trip discriminated unions', () => {
+    const shape = b.union(
+      b.object({ type: b.enum(['c']), r: b.number() }),
+      b.object({ type: b.enum(['s']), side: b.number() }),
+    )
+    const jtd = shape.toJTD()
+    expect(jtd).toEqual({
+      discriminator: 'type',
+      mapping: {
+        c: {
+          properties: { r: { type: 'float64' } },
+          additionalProperties: true,
+        },
+        s: {
+          properties: { side: { type: 'float64' } },
+          additionalProperties: true,
+        },
+      },
+    })
+    const imported = b.fromJTD(jtd)
+    expect(imported.toJTD()).toEqual(jtd)
+    expect(imported.parse({ type: 'c', r: 1 })).toEqual({ type: 'c', r: 1 })
+    expect(imported.parseSafe({ type: 'x' }).success).toBe(false)
+  })
+
+  test('imported widths are validated', () => {
+    const schema = b.fromJTD({ properties: { a: { type: 'int8' } } })
+    expect(schema.parseSafe({ a: 200 })).toEqual({
+      success: false,
+      reason: 'Number is not a valid int8 (at a)',
+    })
+  })
+
+  test('imported objects reject unknown keys by default', () => {
+    const schema = b.fromJTD({ properties: { a: { type: 'int8' } } })
+    expect(schema.parseSafe({ a: 1, b: 2 })).toEqual({
+      success: false,
+      reason: 'Unknown key(s) [b] in strict object',
+    })
+  })
+
+  test('imported objects keep allowed unknown keys', () => {
+    const schema = b.fromJTD({
+      properties: { a: { type: 'string' } },
+      additionalProperties: true,
+    })
+    expect(schema.parse({ a: 'x', b: 2 })).toEqual({ a: 'x', b: 2 })
+  })
+
+  test('reject invalid JTD schemas', () => {
+    expect(() => b.fromJTD({ type: 'int64' })).toThrow(
+      "Invalid JTD schema: Unknown type 'int64' (at type)",
+    )
+    expect(() => b.fromJTD({ ref: 'x' })).toThrow(
+      "Invalid JTD schema: Unknown definition 'x' (at ref)",
+    )
+    expect(() => b.fromJTD({ foo: 1 })).toThrow(
+      "Invalid JTD schema: Unsupported keyword 'foo'",
+    )
+  })
+
+  test('reject schemas which JTD can not represent', () => {
+    expect(() => b.tuple(b.string()).toJTD()).toThrow(
+      'Tuples can not be represented in JTD',
+    )
+  })
+
+  test('reject constraints which JTD can not represent', () => {
+    const unsupported = [
+      {
+        schema: b.object({ n: b.number().min(1).max(5).int() }),
+        message:
+          'Number ranges can not be represented in JTD (at properties.n)',
+      },
+      {
+        schema: b.number().int(),
+        message:
+          'Integers can only be represented in JTD with an integer width',
+      },
+      {
+        schema: b.string().min(1),
+        message: 'String lengths can not be represented in JTD',
+      },
+      {
+        schema: b.string().pattern('a'),
+        message: 'String patterns can not be represented in JTD',
+      },
+      {
+        schema: b.string().format('email'),
+        message: "The format 'email' can not be represented in JTD",
+      },
+      {
+        schema: b.array(b.string()).max(2),
+        message: 'Array lengths can not be represented in JTD',
+      },
+      {
+        schema: b.array(b.string()).unique(),
+        message: 'Unique elements can not be represented in JTD',
+      },
+      {
+        schema: b.array(b.string()).contains(b.string()),
+        message: 'Contains constraints can not be represented in JTD',
+      },
+      {
+        schema: b.object({ a: b.string() }).catchall(b.number()),
+        message:
+          'Catchall schemas of objects with properties can not be represented in JTD',
+      },
+    ]
+    for (const { schema, message } of unsupported) {
+      expect(() => schema.toJTD()).toThrow(message)
+    }
+    expect(b.number().int().width('int8').toJTD()).toEqual({ type: 'int8' })
+  })
+
+  test('round trip metadata', () => {
+    const schema = b.string().describe('Name').deprecated('use fullName')
+    const imported = b.fromJTD(schema.toJTD())
+    expect(imported.toDefinition()).toMatchObject({
+      description: 'Name',
+      deprecated: 'use fullName',
+    })
+    expect(imported.parseSafe('x')).toEqual({
+      success: true,
+      data: 'x',
+      warnings: ['Value is deprecated: use fullName'],
+    })
+  })
+})
@@ -0,0 +1,75 @@
+import { describe, expect, test } from 'bun:test'
+
+import b from '../index'
+
+describe('metadata', () => {
+  test('is surfaced through the definition', () => {
+    const schema = b
+      .string()
+      .describe('Display name')
+      .title('Name')
+      .examples(['Ada'])
+      .meta({ widget: 'text' })
+    expect(schema.toDefinition()).toEqual({
+      type: 'string',
+      optional: false,
+      nullable: false,
+      title: 'Name',
+      description: 'Display name',
+      examples: ['Ada'],
+      meta: { widget: 'text' },
+    })
+  })
+
+  test('is exported to JSON Schema', () => {
+    const schema = b
+      .string()
+      .describe('Display name')
+      .title('Name')
+      .examples(['Ada'])
+      .deprecated('use fullName')
+    expect(schema.toJSONSchema()).toMatchObject({
+      type: 'string',
+      title: 'Name',
+      description: 'Display name',
+      examples: ['Ada'],
+      deprecated: true,
+    })
+  })
+
+  test('does not change parsing', () => {
+    const schema = b.number().describe('Age').title('Age').meta({ unit: 'y' })
+    expect(schema.parseSafe(3)).toEqual({ success: true, data: 3 })
+    expect(schema.parseSafe('3').success).toBe(false)
+  })
+})
+
+describe('deprecated', () => {
+  const schema = b.object({
+    old: b.string().deprecated('use name').optional(),
+    name: b.string(),
+  })
+
+  test('warns when a deprecated value is present', () => {
+    expect(schema.parseSafe({ old: 'a', name: 'b' })).toEqual({
+      success: true,
+      data: { old: 'a', name: 'b' },
+      warnings: ['Value is deprecated: use name (at old)'],
+    })
+  })
+
+  test('does not warn when the value is absent', () => {
+    expect(schema.parseSafe({ name: 'b' })).toEqual({
+      success: true,
+      data: { name: 'b' },
+    })
+  })
+
+  test('warns without a reason', () => {
+    expect(b.string().deprecated().parseSafe('a')).toEqual({
+      success: true,
+      data: 'a',
+      warnings: ['Value is deprecated'],
+    })
+  })
+})
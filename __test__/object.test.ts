@@ -0,0 +1,384 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+const user = b.object({
+  id: b.number(),
+  name: b.string(),
+  address: b.object({ city: b.string(), zip: b.string() }),
+})
+
+describe('composition', () => {
+  test('pick keeps only the given keys', () => {
+    expect(user.pick(['id']).parse({ id: 1, name: 'a' })).toEqual({ id: 1 })
+  })
+
+  test('omit removes the given keys', () => {
+    const schema = user.omit(['address'])
+    expect(schema.parse({ id: 1, name: 'a' })).toEqual({ id: 1, name: 'a' })
+  })
+
+  test('partial makes keys optional', () => {
+    expect(user.partial().parse({})).toEqual({})
+    const schema = user.partial(['name'])
+    expect(schema.parseSafe({ id: 1 }).success).toBe(false)
+    expect(schema.parse({ id: 1, address: { city: 'a', zip: 'b' } })).toEqual({
+      id: 1,
+      address: { city: 'a', zip: 'b' },
+    })
+  })
+
+  test('deepPartial makes nested keys optional', () => {
+    expect(user.deepPartial().parse({ address: {} })).toEqual({ address: {} })
+  })
+
+  test('requiredKeys makes optional keys required', () => {
+    const schema = user.partial().requiredKeys(['id'])
+    expect(schema.parseSafe({}).success).toBe(false)
+    expect(schema.parse({ id: 1 })).toEqual({ id: 1 })
+  })
+
+  test('extend adds and replaces keys', () => {
+    const schema = user
+      .pick(['id'])
+      .extend({ id: b.string(), role: b.string() })
+    expect(schema.parse({ id: 'a', role: 'admin' })).toEqual({
+      id: 'a',
+      role: 'admin',
+    })
+    expect(schema.parseSafe({ id: 1, role: 'admin' }).success).toBe(false)
+  })
+
+  test('keyof creates an enum of the sorted keys', () => {
+    const keys = user.keyof()
+    expect(keys.options()).toEqual(['address', 'id', 'name'])
+    expect(keys.parseSafe('email').success).toBe(false)
+  })
+})
+
+describe('catchall', () => {
+  const schema = b.object({ id: b.number() }).catchall(b.string())
+
+  test('keeps unknown keys valid for the catchall schema', () => {
+    expect(schema.parse({ id: 1, a: 'x' })).toEqual({ id: 1, a: 'x' })
+  })
+
+  test('fails for unknown keys invalid for the catchall schema', () => {
+    expect(schema.parseSafe({ id: 1, a: 2 })).toEqual({
+      success: false,
+      reason: 'expect String, got: Number (at a)',
+    })
+  })
+
+  test('takes precedence over strict', () => {
+    expect(schema.strict().parse({ id: 1, a: 'x' })).toEqual({ id: 1, a: 'x' })
+  })
+
+  test('unknown keys are removed without catchall', () => {
+    const stripped = b.object({ id: b.number() })
+    expect(stripped.parse({ id: 1, a: 'x' })).toEqual({ id: 1 })
+  })
+})
+
+describe('passthrough', () => {
+  const schema = b.object({ id: b.number() }).passthrough()
+
+  test('keeps unknown keys without validating them', () => {
+    const value = { id: 1, a: 'x', b: 2 }
+    expect(schema.parse(value)).toEqual({ id: 1, a: 'x', b: 2 })
+    expect(schema.is({ id: 1, a: 'x' })).toBe(true)
+  })
+
+  test('still validates the shape', () => {
+    expect(schema.parseSafe({ id: 'x', a: 1 })).toEqual({
+      success: false,
+      reason: 'expect Number, got: String (at id)',
+    })
+  })
+
+  test('replaces strict and is replaced by it', () => {
+    const strict = b.object({ id: b.number() }).strict()
+    expect(strict.passthrough().parse({ id: 1, a: 'x' })).toEqual({
+      id: 1,
+      a: 'x',
+    })
+    expect(schema.strict().parseSafe({ id: 1, a: 'x' })).toEqual({
+      success: false,
+      reason: 'Unknown key(s) [a] in strict object',
+    })
+  })
+
+  test('is carried in the definition', () => {
+    const definition = schema.toDefinition()
+    expect(definition).toMatchObject({ passthrough: true })
+    expect(b.fromDefinition(definition).parse({ id: 1, a: 'x' })).toEqual({
+      id: 1,
+      a: 'x',
+    })
+    expect(() =>
+      b.fromDefinition({ ...definition, strict: true, passthrough: true }),
+    ).toThrow("'strict' and 'passthrough' can not be combined")
+  })
+})
+
+describe('property constraints', () => {
+  test('minProperties and maxProperties count the parsed keys', () => {
+    const schema = b
+      .object({ a: b.number().optional() })
+      .catchall(b.number())
+      .minProperties(1)
+      .maxProperties(2)
+    expect(schema.parseSafe({}).success).toBe(false)
+    expect(schema.parse({ a: 1, b: 2 })).toEqual({ a: 1, b: 2 })
+    expect(schema.parseSafe({ a: 1, b: 2, c: 3 }).success).toBe(false)
+  })
+
+  test('removed unknown keys are not counted', () => {
+    const schema = b.object({ a: b.number() }).maxProperties(1)
+    expect(schema.parse({ a: 1, b: 2 })).toEqual({ a: 1 })
+  })
+
+  test('patternProperties validates keys matching a pattern', () => {
+    const schema = b
+      .object({})
+      .patternProperties({ '^n_': b.number(), '^s_': b.string() })
+    expect(schema.parse({ n_a: 1, s_b: 'x', other: true })).toEqual({
+      n_a: 1,
+      s_b: 'x',
+    })
+    expect(schema.parseSafe({ n_a: 'x' })).toEqual({
+      success: false,
+      reason: 'expect Number, got: String (at n_a)',
+    })
+  })
+
+  test('propertyNames validates every key', () => {
+    const schema = b
+      .object({})
+      .catchall(b.number())
+      .propertyNames(b.string().pattern('^[a-z]+$'))
+    expect(schema.parse({ ab: 1 })).toEqual({ ab: 1 })
+    expect(schema.parseSafe({ Ab: 1 }).success).toBe(false)
+  })
+})
+
+describe('prototype checks', () => {
+  class User {
+    name = 'a'
+  }
+
+  test('objects accept class instances and arrays by default', () => {
+    const schema = b.object({})
+    expect(schema.parseSafe(new User()).success).toBe(true)
+    expect(schema.parseSafe([]).success).toBe(true)
+  })
+
+  test('plain only accepts object literals and null prototype objects', () => {
+    const schema = b.object({ name: b.string() }).plain()
+    expect(schema.parse({ name: 'a' })).toEqual({ name: 'a' })
+    const bare = Object.assign(Object.create(null), { name: 'a' })
+    expect(schema.parseSafe(bare).success).toBe(true)
+    expect(schema.parseSafe(new User())).toEqual({
+      success: false,
+      reason: 'Expected a plain object',
+    })
+    expect(schema.parseSafe(new Date()).success).toBe(false)
+  })
+
+  test('rejectArrays rejects arrays', () => {
+    expect(b.object({}).rejectArrays().parseSafe([])).toEqual({
+      success: false,
+      reason: 'Expected an object, got an Array',
+    })
+  })
+
+  test('instanceof checks the prototype chain', () => {
+    class Admin extends User {}
+    const schema = b.instanceof(User)
+    const admin = new Admin()
+    expect(schema.parse(admin)).toBe(admin)
+    expect(schema.parseSafe({ name: 'a' }).success).toBe(false)
+  })
+})
+
+describe('key renaming', () => {
+  test('key() with from reads and renames another key', () => {
+    const schema = b.object({}).key('userId', b.number(), { from: 'user_id' })
+    expect(schema.parse({ user_id: 1 })).toEqual({ userId: 1 })
+  })
+
+  test('camelCaseKeys converts keys', () => {
+    const schema = b
+      .object({ userId: b.number(), createdAt: b.string() })
+      .camelCaseKeys('snake_case')
+    expect(schema.parse({ user_id: 1, created_at: 'now' })).toEqual({
+      userId: 1,
+      createdAt: 'now',
+    })
+  })
+
+  test('fails when keys map to the same key', () => {
+    const schema = b.object({ userId: b.number() }).camelCaseKeys('snake_case')
+    expect(schema.parseSafe({ user_id: 1, userId: 2 })).toEqual({
+      success: false,
+      reason: "Keys 'user_id' and 'userId' both map to key 'userId'",
+    })
+  })
+
+  test('keys are not renamed when the value is invalid', () => {
+    const schema = b.object({ userId: b.number() }).camelCaseKeys('snake_case')
+    const input = { user_id: 'x' }
+    expect(schema.parseSafe(input).success).toBe(false)
+    expect(input).toEqual({ user_id: 'x' })
+  })
+
+  test('rules use the renamed keys', () => {
+    const schema = b
+      .object({ minAge: b.number(), maxAge: b.number() })
+      .camelCaseKeys('snake_case')
+      .lessThan('minAge', 'maxAge')
+    expect(schema.parse({ min_age: 1, max_age: 2 })).toEqual({
+      minAge: 1,
+      maxAge: 2,
+    })
+    expect(schema.parseSafe({ min_age: 2, max_age: 1 }).success).toBe(false)
+  })
+
+  // every schema has its own key order, build several to cover more orders
+  test('chained aliases read every value before renaming', () => {
+    for (let i = 0; i < 8; i++) {
+      const schema = b
+        .object({})
+        .key('a', b.number(), { from: 'b' })
+        .key('b', b.number(), { from: 'c' })
+      expect(schema.parse({ b: 1, c: 2 })).toEqual({ a: 1, b: 2 })
+      expect(schema.is({ b: 1, c: 2 })).toBe(true)
+    }
+  })
+
+  test('swapped aliases exchange the values', () => {
+    for (let i = 0; i < 8; i++) {
+      const schema = b
+        .object({})
+        .key('a', b.number(), { from: 'b' })
+        .key('b', b.string(), { from: 'a' })
+      expect(schema.parse({ b: 1, a: 'x' })).toEqual({ a: 1, b: 'x' })
+      expect(schema.is({ b: 1, a: 'x' })).toBe(true)
+      expect(schema.is({ b: 'x', a: 1 })).toBe(false)
+    }
+  })
+
+  describe('in unions', () => {
+    const schema = b.union(
+      b.object({ userId: b.number() }).camelCaseKeys('snake_case'),
+      b.object({ user_id: b.string() })
+    )
+
+    test('members which fail do not rename keys for later members', () => {
+      const input = { user_id: 'x' }
+      expect(schema.parseSafe(input)).toEqual({
+        success: true,
+        data: { user_id: 'x' },
+      })
+      expect(input).toEqual({ user_id: 'x' })
+    })
+
+    test('is() agrees with parse()', () => {
+      expect(schema.is({ user_id: 'x' })).toBe(true)
+      expect(schema.parse({ user_id: 1 })).toEqual({ userId: 1 })
+      expect(schema.is({ user_id: 1 })).toBe(true)
+      expect(schema.is({ user_id: true })).toBe(false)
+      expect(schema.parseSafe({ user_id: true }).success).toBe(false)
+    })
+  })
+})
+
+describe('cross-field rules', () => {
+  const optional = b.object({
+    a: b.string().optional(),
+    b: b.string().optional(),
+    type: b.string().optional(),
+  })
+
+  test('equalFields', () => {
+    const schema = optional.equalFields('a', 'b')
+    expect(schema.parse({ a: 'x', b: 'x' })).toEqual({ a: 'x', b: 'x' })
+    expect(schema.parseSafe({ a: 'x', b: 'y' })).toEqual({
+      success: false,
+      reason: "Key 'b' does not equal key 'a' (at b)",
+    })
+  })
+
+  test('lessThan compares numbers and dates', () => {
+    const numbers = b
+      .object({ min: b.number(), max: b.number() })
+      .lessThan('min', 'max')
+    expect(numbers.parseSafe({ min: 1, max: 2 }).success).toBe(true)
+    expect(numbers.parseSafe({ min: 2, max: 2 }).success).toBe(false)
+
+    const dates = b
+      .object({ from: b.instanceof(Date), to: b.instanceof(Date) })
+      .lessThan('from', 'to')
+    expect(dates.parseSafe({ from: new Date(2), to: new Date(1) })).toEqual({
+      success: false,
+      reason: "Key 'from' is not less than key 'to' (at to)",
+    })
+  })
+
+  test('lessThan passes when a key is missing', () => {
+    const schema = b
+      .object({ min: b.number().optional(), max: b.number() })
+      .lessThan('min', 'max')
+    expect(schema.parseSafe({ max: 1 }).success).toBe(true)
+  })
+
+  test('requiredIf', () => {
+    const schema = optional.requiredIf('a', { type: 'x' })
+    expect(schema.parseSafe({ type: 'y' }).success).toBe(true)
+    expect(schema.parseSafe({ type: 'x' })).toEqual({
+      success: false,
+      reason: "Key 'a' is required by the values of [type] (at a)",
+    })
+  })
+
+  test('mutuallyExclusive counts null as missing', () => {
+    const schema = b
+      .object({ a: b.string().nullish(), b: b.string().nullish() })
+      .mutuallyExclusive(['a', 'b'])
+    expect(schema.parseSafe({ a: 'x', b: null }).success).toBe(true)
+    expect(schema.parseSafe({ a: 'x', b: 'y' }).success).toBe(false)
+  })
+
+  test('atLeastOneOf', () => {
+    const schema = optional.atLeastOneOf(['a', 'b'])
+    expect(schema.parseSafe({ b: 'x' }).success).toBe(true)
+    expect(schema.parseSafe({})).toEqual({
+      success: false,
+      reason: 'Object requires at least one of the keys [a, b]',
+    })
+  })
+
+  test('rules of omitted keys are dropped', () => {
+    const schema = b
+      .object({ a: b.number(), b: b.number() })
+      .equalFields('a', 'b')
+    expect(schema.omit(['b']).parseSafe({ a: 1 })).toEqual({
+      success: true,
+      data: { a: 1 },
+    })
+    expect(schema.pick(['a', 'b']).parseSafe({ a: 1, b: 2 }).success).toBe(
+      false,
+    )
+  })
+
+  test('rules are kept when merging', () => {
+    const withRule = b
+      .object({ a: b.number(), c: b.number() })
+      .equalFields('a', 'c')
+    const schema = b.object({ x: b.number() }).merge(withRule)
+    expect(schema.parseSafe({ x: 1, a: 1, c: 2 })).toEqual({
+      success: false,
+      reason: "Key 'c' does not equal key 'a' (at c)",
+    })
+    expect(schema.parseSafe({ x: 1, a: 1, c: 1 }).success).toBe(true)
+  })
+})
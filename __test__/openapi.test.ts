@@ -0,0 +1,89 @@
+import { describe, expect, test } from 'bun:test'
+
+import b from '../index'
+
+const Cat = b.object({ kind: b.enum(['cat']), lives: b.number().int() })
+const Dog = b.object({ kind: b.enum(['dog']), good: b.boolean() })
+
+describe('toOpenAPIComponents', () => {
+  test('links named schemas with $ref', () => {
+    const Owner = b.object({ cat: Cat, name: b.string() })
+    expect(b.toOpenAPIComponents({ Cat, Owner })).toEqual({
+      schemas: {
+        Cat: {
+          type: 'object',
+          properties: {
+            kind: { type: 'string', enum: ['cat'] },
+            lives: { type: 'integer' },
+          },
+          required: ['kind', 'lives'],
+        },
+        Owner: {
+          type: 'object',
+          properties: {
+            cat: { $ref: '#/components/schemas/Cat' },
+            name: { type: 'string' },
+          },
+          required: ['cat', 'name'],
+        },
+      },
+    })
+  })
+
+  test('maps discriminated unions of named schemas', () => {
+    const Pet = b.union(Cat, Dog)
+    const { schemas } = b.toOpenAPIComponents({ Cat, Dog, Pet })
+    expect(schemas.Pet).toEqual({
+      anyOf: [
+        { $ref: '#/components/schemas/Cat' },
+        { $ref: '#/components/schemas/Dog' },
+      ],
+      discriminator: {
+        propertyName: 'kind',
+        mapping: {
+          cat: '#/components/schemas/Cat',
+          dog: '#/components/schemas/Dog',
+        },
+      },
+    })
+  })
+
+  test('leaves out the mapping for inline members', () => {
+    const Pet = b.union(Cat, Dog)
+    const { schemas } = b.toOpenAPIComponents({ Pet })
+    expect(schemas.Pet.discriminator).toEqual({ propertyName: 'kind' })
+    expect(schemas.Pet.anyOf).toHaveLength(2)
+  })
+
+  test('carries nullable, deprecated and description', () => {
+    const User = b
+      .object({
+        nick: b.string().nullable(),
+        old: b.string().deprecated('gone').optional(),
+      })
+      .describe('A user')
+    expect(b.toOpenAPIComponents({ User }).schemas.User).toEqual({
+      type: 'object',
+      properties: {
+        nick: { type: ['string', 'null'] },
+        old: { type: 'string', deprecated: true },
+      },
+      required: ['nick'],
+      description: 'A user',
+    })
+  })
+
+  test('references described variants of named schemas', () => {
+    const Owner = b.object({ dog: Dog.describe('Their dog') })
+    const { schemas } = b.toOpenAPIComponents({ Dog, Owner })
+    expect(schemas.Owner.properties.dog).toMatchObject({
+      $ref: '#/components/schemas/Dog',
+    })
+  })
+
+  test('rejects names which can not be referenced', () => {
+    expect(() => b.toOpenAPIComponents({ 'a b': Cat })).toThrow(
+      "Component name 'a b' can only contain letters, digits, '.', '-' and '_'",
+    )
+  })
+})
@@ -0,0 +1,30 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+describe('preprocess', () => {
+  test('runs before validation', () => {
+    const schema = b.preprocess(value => Number(value), b.number().int())
+    expect(schema.parse('42')).toBe(42)
+    expect(schema.parseSafe('4.2').success).toBe(false)
+  })
+
+  test('receives undefined for missing object keys', () => {
+    const schema = b.object({
+      count: b.preprocess(value => value ?? 0, b.number()),
+    })
+    expect(schema.parse({})).toEqual({ count: 0 })
+  })
+
+  test('runs on every element', () => {
+    const schema = b.array(
+      b.preprocess(value => String(value).trim(), b.string())
+    )
+    expect(schema.parse([' a', 1])).toEqual(['a', '1'])
+  })
+
+  test('is used by is()', () => {
+    const schema = b.preprocess(value => Number(value), b.number().int())
+    expect(schema.is('1')).toBe(true)
+    expect(schema.is('1.5')).toBe(false)
+  })
+})
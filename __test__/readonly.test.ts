@@ -0,0 +1,53 @@
+import { describe, expect, test } from 'bun:test'
+
+import b from '../index'
+
+describe('readonly', () => {
+  test('freezes objects shallowly by default', () => {
+    const schema = b.object({ a: b.object({ c: b.string() }) }).readonly()
+    const parsed = schema.parse({ a: { c: 'x' } })
+    expect(Object.isFrozen(parsed)).toBe(true)
+    expect(Object.isFrozen(parsed.a)).toBe(false)
+  })
+
+  test('freezes nested values when deep', () => {
+    const schema = b
+      .object({ a: b.object({ c: b.string() }), l: b.array(b.number()) })
+      .readonly(true)
+    const parsed = schema.parse({ a: { c: 'x' }, l: [1] })
+    expect(Object.isFrozen(parsed)).toBe(true)
+    expect(Object.isFrozen(parsed.a)).toBe(true)
+    expect(Object.isFrozen(parsed.l)).toBe(true)
+  })
+
+  test('freezes arrays and tuples', () => {
+    const array = b.array(b.number()).readonly().parse([1])
+    const tuple = b.tuple(b.number()).readonly().parse([1])
+    expect(Object.isFrozen(array)).toBe(true)
+    expect(Object.isFrozen(tuple)).toBe(true)
+  })
+
+  test('leaves invalid input unfrozen', () => {
+    const input = { a: 1 }
+    const schema = b.object({ a: b.string() }).readonly()
+    expect(schema.parseSafe(input).success).toBe(false)
+    expect(Object.isFrozen(input)).toBe(false)
+  })
+
+  test('freezes values parsed asynchronously', async () => {
+    const schema = b
+      .object({ a: b.string().refine(async () => true) })
+      .readonly()
+    expect(Object.isFrozen(await schema.parseAsync({ a: 'x' }))).toBe(true)
+  })
+
+  test('keeps the path of async errors in nested values', async () => {
+    const schema = b.object({
+      a: b.object({ c: b.string().refine(async () => false, 'Bad') }).readonly(),
+    })
+    expect(await schema.parseSafeAsync({ a: { c: 'x' } })).toEqual({
+      success: false,
+      reason: 'Bad (at a.c)',
+    })
+  })
+})
@@ -0,0 +1,71 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+describe('refine', () => {
+  test('passes values the check accepts', () => {
+    const schema = b.number().refine(value => value > 0, 'Must be positive')
+    expect(schema.parse(1)).toBe(1)
+  })
+
+  test('fails with the given message', () => {
+    const schema = b.number().refine(value => value > 0, 'Must be positive')
+    expect(() => schema.parse(-1)).toThrow('Must be positive')
+  })
+
+  test('includes the path of nested values', () => {
+    const schema = b.array(
+      b.string().refine(value => value.length > 1, 'Too short')
+    )
+    expect(schema.parseSafe(['ab', 'c'])).toEqual({
+      success: false,
+      reason: 'Too short (at 1)',
+    })
+  })
+
+  test('keeps messages which look like a path', () => {
+    const schema = b.object({
+      x: b.number().refine(() => false, 'bad (at home)'),
+    })
+    expect(schema.parseSafe({ x: 1 })).toEqual({
+      success: false,
+      reason: 'bad (at home) (at x)',
+    })
+  })
+
+  test('runs after the base validation', () => {
+    let calls = 0
+    const schema = b.string().refine(() => {
+      calls++
+      return true
+    })
+    expect(schema.parseSafe(1).success).toBe(false)
+    expect(calls).toBe(0)
+  })
+
+  test('rethrows errors thrown by the check', () => {
+    const schema = b.string().refine(() => {
+      throw new Error('boom')
+    })
+    expect(() => schema.parse('a')).toThrow('boom')
+  })
+})
+
+describe('superRefine', () => {
+  test('fails with the first issue and its path', () => {
+    const schema = b
+      .object({ password: b.string(), confirm: b.string() })
+      .superRefine((value, ctx) => {
+        if (value.password !== value.confirm) {
+          ctx.addIssue({ message: 'Passwords do not match', path: ['confirm'] })
+        }
+      })
+    expect(schema.parseSafe({ password: 'a', confirm: 'b' })).toEqual({
+      success: false,
+      reason: 'Passwords do not match (at confirm)',
+    })
+    expect(schema.parse({ password: 'a', confirm: 'a' })).toEqual({
+      password: 'a',
+      confirm: 'a',
+    })
+  })
+})
@@ -0,0 +1,68 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+describe('transform', () => {
+  test('returns the transformed value', () => {
+    expect(b.string().transform(Number).parse('12')).toBe(12)
+  })
+
+  test('writes transformed properties and elements back', () => {
+    const schema = b.object({
+      id: b.string().transform(Number),
+      tags: b.array(b.string().transform(tag => tag.toUpperCase())),
+    })
+    expect(schema.parse({ id: '1', tags: ['a', 'b'] })).toEqual({
+      id: 1,
+      tags: ['A', 'B'],
+    })
+  })
+
+  test('passes undefined and null of optional schemas through', () => {
+    const schema = b.string().transform(Number).optional()
+    expect(schema.parse(undefined)).toBeUndefined()
+  })
+})
+
+describe('pipe', () => {
+  test('parses the transformed value with the next schema', () => {
+    const schema = b.string().transform(Number).pipe(b.number().min(10))
+    expect(schema.parse('12')).toBe(12)
+    expect(schema.parseSafe('5').success).toBe(false)
+  })
+})
+
+describe('transform in unions', () => {
+  const schema = b.union(
+    b.object({ a: b.string().transform(Number), c: b.number() }),
+    b.object({ a: b.string() })
+  )
+
+  test('members which fail do not change the input for later members', () => {
+    const input = { a: '1', c: 'x' }
+    expect(schema.parseSafe(input)).toEqual({ success: true, data: { a: '1' } })
+    expect(input).toEqual({ a: '1', c: 'x' })
+  })
+
+  test('the input is unchanged when no member matches', () => {
+    const input = { a: 1, c: 'x' }
+    expect(schema.parseSafe(input).success).toBe(false)
+    expect(input).toEqual({ a: 1, c: 'x' })
+  })
+
+  test('nested containers of failed members are not changed', () => {
+    const nested = b.union(
+      b.object({
+        items: b.array(b.string().transform(Number)),
+        ok: b.boolean(),
+      }),
+      b.object({ items: b.array(b.string()) })
+    )
+    const input = { items: ['1'], ok: 'no' }
+    expect(nested.parse(input)).toEqual({ items: ['1'] })
+    expect(input).toEqual({ items: ['1'], ok: 'no' })
+  })
+
+  test('the matching member transforms the value', () => {
+    expect(schema.parse({ a: '1', c: 2 })).toEqual({ a: 1, c: 2 })
+  })
+})
@@ -0,0 +1,54 @@
+import { describe, expect, test } from 'bun:test'
+import b from '../index'
+
+describe('tuple length', () => {
+  test('requires the exact length without optional or rest elements', () => {
+    const schema = b.tuple(b.string(), b.number())
+    expect(schema.parse(['a', 1])).toEqual(['a', 1])
+    expect(schema.parseSafe(['a'])).toEqual({
+      success: false,
+      reason: 'Tuple length (1) does not equal expected length (2)',
+    })
+  })
+
+  test('trailing optional elements can be absent', () => {
+    const schema = b.tuple(b.string(), b.number().optional())
+    expect(schema.parse(['a'])).toEqual(['a'])
+    expect(schema.parseSafe([])).toEqual({
+      success: false,
+      reason: 'Tuple length (0) is lower than minimum length (1)',
+    })
+    expect(schema.parseSafe(['a', 1, 2])).toEqual({
+      success: false,
+      reason: 'Tuple length (3) is higher than maximum length (2)',
+    })
+  })
+
+  test('rest elements are parsed with the rest schema', () => {
+    const schema = b.tuple(b.string()).rest(b.number())
+    expect(schema.parse(['a'])).toEqual(['a'])
+    expect(schema.parse(['a', 1, 2])).toEqual(['a', 1, 2])
+    expect(schema.parseSafe(['a', 1, 'x'])).toEqual({
+      success: false,
+      reason: 'expect Number, got: String (at 2)',
+    })
+  })
+})
+
+describe('element errors', () => {
+  test('include the index of the element', () => {
+    const schema = b.tuple(b.string(), b.number())
+    expect(schema.parseSafe(['a', 'b'])).toEqual({
+      success: false,
+      reason: 'expect Number, got: String (at 1)',
+    })
+  })
+
+  test('include the name of named elements', () => {
+    const schema = b.tuple.named(['name', b.string()], ['price', b.number()])
+    expect(schema.parseSafe(['a', 'b'])).toEqual({
+      success: false,
+      reason: 'expect Number, got: String (at 1:price)',
+    })
+  })
+})
@@ -7,10 +7,10 @@ then
     BValues=(BUndefined BNull BBoolean BNumber BString BArray BObject BUnion)
     joined=$(IFS=, ; echo "${BValues[*]}")
 
-    declare -a values=("BUndefined" "BNull" "BBoolean" "BNumber" "BString" "BArray" "BObject" "BUnion" "BTuple")
+    declare -a values=("BUndefined" "BNull" "BBoolean" "BNumber" "BString" "BEnum" "BArray" "BObject" "BInstance" "BUnion" "BTuple" "BConditional")
 
     # sed -i "1s|^|import {$joined} from '../index'\n\n|" $path;
-    sed -i "1s|^|import { type ParseSafe } from './index'\n\n|" $path;
+    sed -i "1s|^|import { type BValue, type DeepPartial, type DeepReadonly, type InferBObjectParseType, type ParseSafe, type SchemaDefinition, type SchemaMetadata } from './index'\n\n|" $path;
 
     # change class names
     for value in ${values[@]}
@@ -21,4 +21,21 @@ then
 
     # add generics to merge methods
     sed -i "s/merge/merge<T>/g" $path;
+
+    # add generics to methods changing the output type
+    sed -i "s/ transform(/ transform<T>(/g" $path;
+    sed -i "s/ pipe(/ pipe<T>(/g" $path;
+    sed -i "s/ catchall(/ catchall<T>(/g" $path;
+    sed -i "s/ rest(/ rest<T>(/g" $path;
+    sed -i "s/ brand(/ brand<B extends string>(/g" $path;
+    sed -i "s/ readonly(/ readonly<D extends boolean = false>(/g" $path;
+
+    # add generics to object shape methods
+    objectKey="keyof Exclude<R, null | undefined>"
+    sed -i "s/ pick(/ pick<K extends $objectKey>(/g" $path;
+    sed -i "s/ omit(/ omit<K extends $objectKey>(/g" $path;
+    sed -i "s/ partial(/ partial<K extends $objectKey = $objectKey>(/g" $path;
+    sed -i "s/ requiredKeys(/ requiredKeys<K extends $objectKey = $objectKey>(/g" $path;
+    sed -i "s/ extend(/ extend<T extends Record<string, BValue>>(/g" $path;
+    sed -i "s/ key(/ key<K extends string, T>(/g" $path;
 fi
@@ -1,20 +1,146 @@
 import native from './native'
 
-export type ParseSafe<T> = { success: boolean } & (
+export type {
+  ContainsOptions,
+  Issue,
+  JsonSchemaOptions,
+  KeyOptions,
+  RefinementContext,
+} from './native'
+
+export type ParseSafe<T> = { success: boolean; warnings?: string[] } & (
   | { success: true; data: T }
   | { success: false; reason: string }
 )
 
+export type SchemaMetadata<T> = {
+  title?: string
+  description?: string
+  examples?: T[]
+  /** Deprecation reason, empty when no reason was given */
+  deprecated?: string
+  meta?: Record<string, unknown>
+}
+
+export type StringFormat =
+  | 'date-time'
+  | 'date'
+  | 'time'
+  | 'email'
+  | 'hostname'
+  | 'ipv4'
+  | 'ipv6'
+  | 'uri'
+  | 'uuid'
+
+export type NumberWidth =
+  | 'int8'
+  | 'uint8'
+  | 'int16'
+  | 'uint16'
+  | 'int32'
+  | 'uint32'
+  | 'float32'
+  | 'float64'
+
+type DefinitionBase = SchemaMetadata<unknown> & {
+  optional: boolean
+  nullable: boolean
+  brand?: string
+  /** Set when values are preprocessed before parsing */
+  preprocess?: true
+  /** Effects in the order they run, functions themselves are not included */
+  effects?: (
+    | { type: 'refine'; message: string }
+    | { type: 'superRefine' | 'transform' }
+    | { type: 'pipe'; schema: SchemaDefinition }
+  )[]
+}
+
+type ArrayDefinitionOptions = {
+  holes?: 'reject' | 'compact'
+  arrayLike?: true
+  readonly?: 'shallow' | 'deep'
+}
+
+export type ObjectRuleDefinition =
+  | { type: 'equalFields' | 'lessThan'; a: string; b: string }
+  | { type: 'requiredIf'; key: string; when: Record<string, unknown> }
+  | { type: 'mutuallyExclusive' | 'atLeastOneOf'; keys: string[] }
+
+/** JSON-serializable description of a schema, returned by `toDefinition()` */
+export type SchemaDefinition = DefinitionBase &
+  (
+    | { type: 'undefined' | 'null' }
+    | { type: 'boolean'; value?: boolean }
+    | {
+        type: 'number'
+        min?: number
+        max?: number
+        int?: true
+        width?: NumberWidth
+      }
+    | {
+        type: 'string'
+        length?: number
+        min?: number
+        max?: number
+        pattern?: string
+        format?: StringFormat
+      }
+    | { type: 'enum'; values: string[] }
+    | ({
+        type: 'array'
+        element: SchemaDefinition
+        length?: number
+        min?: number
+        max?: number
+        /** `true` for unique elements, key path for elements unique by that key */
+        unique?: true | string
+        contains?: { schema: SchemaDefinition; min: number; max?: number }
+      } & ArrayDefinitionOptions)
+    | {
+        type: 'object'
+        shape: Record<string, SchemaDefinition>
+        strict?: true
+        passthrough?: true
+        catchall?: SchemaDefinition
+        patternProperties?: Record<string, SchemaDefinition>
+        propertyNames?: SchemaDefinition
+        minProperties?: number
+        maxProperties?: number
+        plain?: true
+        rejectArrays?: true
+        /** Maps input keys to keys of the shape */
+        aliases?: Record<string, string>
+        keyCase?: 'snake_case' | 'kebab-case'
+        rules?: ObjectRuleDefinition[]
+        readonly?: 'shallow' | 'deep'
+      }
+    | { type: 'instance'; className: string }
+    | { type: 'union'; options: SchemaDefinition[]; exclusive?: true }
+    | ({
+        type: 'tuple'
+        elements: SchemaDefinition[]
+        names?: string[]
+        rest?: SchemaDefinition
+      } & ArrayDefinitionOptions)
+    | { type: 'conditional'; if: SchemaDefinition; then: SchemaDefinition; else?: SchemaDefinition }
+  )
+
 export const {
   BUndefined,
   BNull,
   BBoolean,
   BNumber,
   BString,
+  BEnum,
   BArray,
   BObject,
+  BInstance,
   BUnion,
   BTuple,
+  BConditional,
 } = native
 
 type BUndefined<T> = native.BUndefined<T>
@@ -22,10 +148,13 @@ type BNull<T> = native.BNull<T>
 type BBoolean<T> = native.BBoolean<T>
 type BNumber<T> = native.BNumber<T>
 type BString<T> = native.BString<T>
+type BEnum<T> = native.BEnum<T>
 type BArray<T> = native.BArray<T>
 type BObject<T> = native.BObject<T>
+type BInstance<T> = native.BInstance<T>
 type BUnion<T> = native.BUnion<T>
 type BTuple<T> = native.BTuple<T>
+type BConditional<T> = native.BConditional<T>
 
 export type BValue<T = unknown> =
   | BUndefined<T>
@@ -33,10 +162,13 @@ export type BValue<T = unknown> =
   | BBoolean<T>
   | BNumber<T>
   | BString<T>
+  | BEnum<T>
   | BArray<T>
   | BObject<T>
+  | BInstance<T>
   | BUnion<T>
   | BTuple<T>
+  | BConditional<T>
 
 /**
  * Infer the `parse()` return type of a BValue schema
@@ -51,19 +183,54 @@ export type InferParseSafe<T extends BValue> = ParseSafe<InferParse<T>>
 type ArrayElement<ArrayType extends readonly unknown[]> =
   ArrayType extends readonly (infer ElementType)[] ? ElementType : never
 
-type InferBObjectParseType<T extends Record<string, BValue>> = {
+export type InferBObjectParseType<T extends Record<string, BValue>> = {
   [key in keyof T]: InferParse<T[key]>
 }
 
-type InferBTupleParseType<T extends readonly BValue[]> = {
+export type DeepPartial<T> = T extends readonly unknown[]
+  ? { [key in keyof T]: DeepPartial<T[key]> }
+  : T extends object
+  ? { [key in keyof T]?: DeepPartial<T[key]> }
+  : T
+
+export type DeepReadonly<T> = T extends (...args: any[]) => unknown
+  ? T
+  : T extends object
+  ? { readonly [key in keyof T]: DeepReadonly<T[key]> }
+  : T
+
+type InferBTupleElements<T extends readonly BValue[]> = {
   [key in keyof T]: InferParse<T[key]>
 }
 
+type IsOptionalTail<T extends readonly BValue[]> = T extends readonly []
+  ? true
+  : T extends readonly [
+      infer First extends BValue,
+      ...infer Rest extends readonly BValue[]
+    ]
+  ? undefined extends InferParse<First>
+    ? IsOptionalTail<Rest>
+    : false
+  : false
+
+// trailing elements accepting undefined are optional, like `[string, number?]`
+type InferBTupleParseType<T extends readonly BValue[]> = T extends readonly [
+  infer First extends BValue,
+  ...infer Rest extends readonly BValue[]
+]
+  ? IsOptionalTail<T> extends true
+    ? [InferParse<First>?, ...InferBTupleParseType<Rest>]
+    : [InferParse<First>, ...InferBTupleParseType<Rest>]
+  : InferBTupleElements<T>
+
 const undefined = native.BUndefined.default
 const Null = native.BNull.default
 const boolean = native.BBoolean.default
 const number = native.BNumber.default
 const string = native.BString.default
+const enumeration = <const T extends readonly string[]>(values: T) =>
+  native.BEnum.new(values) as BEnum<T[number]>
 const array = <T extends BValue>(schema: T) =>
   native.BArray._fromWrapped(schema._toWrapped()) as BArray<InferParse<T>[]>
 const object = <T extends Record<string, BValue>>(schema: T) =>
@@ -75,14 +242,86 @@ const object = <T extends Record<string, BValue>>(schema: T) =>
       ])
     )
   ) as BObject<InferBObjectParseType<T>>
+const instanceOf = <T extends abstract new (...args: any) => any>(
+  cls: T
+) => native.BInstance.new(cls) as BInstance<InstanceType<T>>
 const union = <T extends readonly BValue[]>(...schemas: T) =>
   native.BUnion._fromWrapped(
     schemas.map(schema => schema._toWrapped())
   ) as BUnion<InferParse<ArrayElement<T>>>
-const tuple = <T extends readonly BValue[]>(...schemas: T) =>
-  native.BTuple._fromWrapped(
-    schemas.map(schema => schema._toWrapped())
-  ) as BTuple<InferBTupleParseType<T>>
+type NamedTupleElements<T extends readonly (readonly [string, BValue])[]> = {
+  [key in keyof T]: T[key] extends readonly [string, infer S] ? S : never
+}
+
+/**
+ * Tuple with a name for every element, names are included in error paths like `1:price`
+ */
+const namedTuple = <const T extends readonly (readonly [string, BValue])[]>(
+  ...elements: T
+) =>
+  native.BTuple._fromWrappedNamed(
+    elements.map(([name]) => name),
+    elements.map(([, schema]) => schema._toWrapped())
+  ) as BTuple<
+    InferBTupleParseType<Extract<NamedTupleElements<T>, readonly BValue[]>>
+  >
+const tuple = Object.assign(
+  <T extends readonly BValue[]>(...schemas: T) =>
+    native.BTuple._fromWrapped(
+      schemas.map(schema => schema._toWrapped())
+    ) as BTuple<InferBTupleParseType<T>>,
+  { named: namedTuple }
+)
+
+/**
+ * Parse with `thenSchema` when the value is valid for `ifSchema`, otherwise with `elseSchema`.
+ * Without `elseSchema` values not matching `ifSchema` are passed unchanged.
+ */
+const conditional = <I extends BValue, T extends BValue, E extends BValue = BValue>(
+  ifSchema: I,
+  thenSchema: T,
+  elseSchema?: E
+) =>
+  native.BConditional._fromWrapped(
+    ifSchema._toWrapped(),
+    thenSchema._toWrapped(),
+    elseSchema?._toWrapped()
+  ) as BConditional<InferParse<T> | InferParse<E>>
+
+/**
+ * Run `fn` on the raw value before it is parsed by `schema`
+ */
+const preprocess = <T extends BValue>(
+  fn: (value: unknown) => unknown,
+  schema: T
+) => schema._preprocess(fn) as T
+
+/**
+ * Build a schema from a definition returned by `toDefinition()`, e.g. loaded from a database.
+ * Throws for invalid definitions and for schemas with functions, which can not be restored.
+ */
+const fromDefinition = (definition: SchemaDefinition) =>
+  native.fromDefinition(definition)
+
+/**
+ * Build a schema from a JSON Schema document (draft-07 or 2020-12).
+ * Throws for keywords which can not be represented, instead of ignoring them.
+ */
+const fromJSONSchema = (schema: Record<string, unknown>) =>
+  native.fromJSONSchema(schema)
+
+/**
+ * Create the `components` object of an OpenAPI 3.1 document, schemas used within
+ * other schemas are referenced with `$ref` to their component.
+ */
+const toOpenAPIComponents = (schemas: Record<string, BValue>) =>
+  native.toOpenAPIComponents(schemas)
+
+/**
+ * Build a schema from a JSON Type Definition (RFC 8927) schema.
+ * Throws for the empty form and recursive definitions, which can not be represented.
+ */
+const fromJTD = (schema: Record<string, unknown>) => native.fromJTD(schema)
 
 export default {
   undefined,
@@ -90,10 +329,18 @@ export default {
   boolean,
   number,
   string,
+  enum: enumeration,
   array,
   object,
+  instanceof: instanceOf,
   union,
   tuple,
+  conditional,
+  preprocess,
+  fromDefinition,
+  fromJSONSchema,
+  toOpenAPIComponents,
+  fromJTD,
 }
 
 // type NativeBValue =
@@ -1,33 +1,141 @@
-import { type ParseSafe } from './index'
+import { type BValue, type DeepPartial, type DeepReadonly, type InferBObjectParseType, type ParseSafe, type SchemaDefinition, type SchemaMetadata } from './index'
 
 /* tslint:disable */
 /* eslint-disable */
 
 /* auto-generated by NAPI-RS */
 
+export interface ContainsOptions {
+  /** Minimum amount of matching elements, defaults to 1. */
+  min?: number
+  max?: number
+}
+export interface Issue {
+  message: string
+  /** Path relative to the refined value. */
+  path?: Array<string | number>
+}
+export interface JsonSchemaOptions {
+  /** JSON Schema version of the document, defaults to `'2020-12'`. */
+  draft?: '2020-12' | 'draft-07'
+}
+export interface KeyOptions {
+  /** Key in the input value, renamed to the key of the shape when parsing. */
+  from?: string
+}
 export interface ParseSafeRes {
   success: boolean
   data?: unknown
   reason?: string
+  /** Warnings for deprecated values present in the input. */
+  warnings?: Array<string>
 }
+/**
+ * Build a schema from a definition returned by `toDefinition()`, without evaluating JS code.
+ * Schemas with effects, preprocess functions or instance checks can not be restored.
+ */
+export function fromDefinition(definition: SchemaDefinition): BValue
+/**
+ * Build a schema from a JSON Schema document, supporting a subset of draft-07 and 2020-12.
+ * Fails for keywords which can not be represented instead of ignoring them.
+ */
+export function fromJSONSchema(schema: Record<string, unknown>): BValue
+/** Build a schema from a JSON Type Definition (RFC 8927) schema. */
+export function fromJTD(schema: Record<string, unknown>): BValue
+/**
+ * Create the `components` object of an OpenAPI 3.1 document for named schemas.
+ * Schemas used within other schemas are referenced with `$ref` to their component.
+ */
+export function toOpenAPIComponents(schemas: Record<string, BValue>): { schemas: Record<string, Record<string, unknown>> }
 export class BArray<R> {
   optional(): BArray<R | undefined>
   nullable(): BArray<R | null>
   nullish(): BArray<R | null | undefined>
   required(): BArray<Exclude<R, undefined>>
   nonNullable(): BArray<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BArray<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BArray<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BArray<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   /** For internal use only! */
   static _fromWrapped(wrapped: BWrapped): BArray<unknown>
-  parse(value: unknown): R
+  /** Freeze the parsed value with `Object.freeze`, nested objects and arrays as well when `deep`. */
+  readonly<D extends boolean = false>(deep?: D): BArray<(D extends true ? DeepReadonly<Exclude<R, null | undefined>> : Readonly<Exclude<R, null | undefined>>) | Extract<R, null | undefined>>
   length(value: number): BArray<R>
   min(value: number): BArray<R>
   max(value: number): BArray<R>
+  /**
+   * How holes of sparse arrays are handled, by default they are parsed as undefined.
+   * Compacting removes holes in place, moving later elements to lower indices.
+   */
+  holes(policy: 'undefined' | 'reject' | 'compact'): BArray<R>
+  /** Accept `arguments` objects and typed arrays, parsing converts them to arrays. */
+  arrayLike(): BArray<R>
+  /** Require at least 1 element. */
+  nonempty(): BArray<[Exclude<R, null | undefined>[number], ...Exclude<R, null | undefined>[number][]] | Extract<R, null | undefined>>
+  /** Require elements to be unique, primitives are compared by value and objects by identity. */
+  unique(): BArray<R>
+  /** Require the values at `keyPath` of the elements to be unique, e.g. `'id'` or `'item.id'`. */
+  uniqueBy(keyPath: string): BArray<R>
+  /** Require the amount of elements valid for `schema` to be within `min` (default 1) and `max`. */
+  contains(schema: BValue, options?: ContainsOptions): BArray<R>
 }
 export class BBoolean<R> {
   optional(): BBoolean<R | undefined>
@@ -35,31 +143,352 @@ export class BBoolean<R> {
   nullish(): BBoolean<R | null | undefined>
   required(): BBoolean<Exclude<R, undefined>>
   nonNullable(): BBoolean<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BBoolean<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BBoolean<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BBoolean<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   static default(): BBoolean<boolean>
-  parse(value: unknown): R
   isTrue(): BBoolean<R>
   isFalse(): BBoolean<R>
 }
+/** Parses with `then` when the value is valid for `condition`, like JSON Schema's `if`/`then`/`else`. */
+export class BConditional<R> {
+  optional(): BConditional<R | undefined>
+  nullable(): BConditional<R | null>
+  nullish(): BConditional<R | null | undefined>
+  required(): BConditional<Exclude<R, undefined>>
+  nonNullable(): BConditional<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BConditional<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
+  /**
+   * For internal use only!
+   * Create BWrapped instance of this value to be used by other methods
+   */
+  _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BConditional<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BConditional<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
+  parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
+  /** For internal use only! */
+  static _fromWrapped(condition: BWrapped, then: BWrapped, otherwise?: BWrapped | undefined | null): unknown
+}
+export class BEnum<R> {
+  optional(): BEnum<R | undefined>
+  nullable(): BEnum<R | null>
+  nullish(): BEnum<R | null | undefined>
+  required(): BEnum<Exclude<R, undefined>>
+  nonNullable(): BEnum<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BEnum<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
+  /**
+   * For internal use only!
+   * Create BWrapped instance of this value to be used by other methods
+   */
+  _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BEnum<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BEnum<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
+  parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
+  /** For internal use only! */
+  static new(values: Array<string>): BEnum<unknown>
+  /** Allowed values of the enum. */
+  options(): R[]
+}
+export class BInstance<R> {
+  optional(): BInstance<R | undefined>
+  nullable(): BInstance<R | null>
+  nullish(): BInstance<R | null | undefined>
+  required(): BInstance<Exclude<R, undefined>>
+  nonNullable(): BInstance<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BInstance<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
+  /**
+   * For internal use only!
+   * Create BWrapped instance of this value to be used by other methods
+   */
+  _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BInstance<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BInstance<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
+  parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
+  /** For internal use only! */
+  static new(class: (...args: any[]) => any): BInstance<unknown>
+}
 export class BNull<R> {
   optional(): BNull<R | undefined>
   nullable(): BNull<R | null>
   nullish(): BNull<R | null | undefined>
   required(): BNull<Exclude<R, undefined>>
   nonNullable(): BNull<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BNull<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BNull<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BNull<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   static default(): BNull<null>
-  parse(value: unknown): R
 }
 export class BNumber<R> {
   optional(): BNumber<R | undefined>
@@ -67,16 +496,73 @@ export class BNumber<R> {
   nullish(): BNumber<R | null | undefined>
   required(): BNumber<Exclude<R, undefined>>
   nonNullable(): BNumber<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BNumber<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BNumber<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BNumber<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   static default(): BNumber<number>
-  parse(value: unknown): R
   min(min: number): BNumber<R>
   max(max: number): BNumber<R>
+  int(): BNumber<R>
+  /** Restrict the number to a numeric type of JSON Type Definition, e.g. integers of `'uint8'`. */
+  width(width: 'int8' | 'uint8' | 'int16' | 'uint16' | 'int32' | 'uint32' | 'float32' | 'float64'): BNumber<R>
   positive(): BNumber<R>
   negative(): BNumber<R>
 }
@@ -86,15 +572,69 @@ export class BObject<R> {
   nullish(): BObject<R | null | undefined>
   required(): BObject<Exclude<R, undefined>>
   nonNullable(): BObject<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BObject<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BObject<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BObject<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   /** For internal use only! */
   static new(schema: Record<string, BWrapped>): BObject<unknown>
-  parse(value: unknown): R
   /**
    * Create a new object schema by merging 2 objects.
    * Optional and Nullable parameters of both objects are ignored for new object.
@@ -105,8 +645,90 @@ export class BObject<R> {
    * When not strict onknown object keys are removed from the value, when strict parsing fails.
    */
   strict(): BObject<R>
+  /** Keep unknown keys when parsing without validating their values, replaces the strict option. */
+  passthrough(): BObject<(Exclude<R, null | undefined> & Record<string, unknown>) | Extract<R, null | undefined>>
   /** Removes strict option. */
   unStrict(): BObject<R>
+  /**
+   * Keep unknown keys when parsing and validate their values with `schema`.
+   * Takes precedence over the strict option.
+   */
+  catchall<T>(schema: BValue<T>): BObject<(Exclude<R, null | undefined> & Record<string, T>) | Extract<R, null | undefined>>
+  /**
+   * Keep unknown keys matching a pattern when parsing and validate their values with its schema.
+   * When a key matches multiple patterns it has to be valid for all of them.
+   */
+  patternProperties(patterns: Record<string, BValue>): BObject<R>
+  /** Validate every key of the parsed object with a string schema. */
+  propertyNames(schema: BValue<string>): BObject<R>
+  /** Minimum amount of keys of the parsed object, unknown keys which are removed are not counted. */
+  minProperties(value: number): BObject<R>
+  /** Maximum amount of keys of the parsed object, unknown keys which are removed are not counted. */
+  maxProperties(value: number): BObject<R>
+  /**
+   * Only allow plain objects, created by an object literal or `Object.create(null)`.
+   * Arrays, class instances and objects like Dates are rejected.
+   */
+  plain(): BObject<R>
+  /** Reject arrays, which are otherwise accepted as objects with index keys. */
+  rejectArrays(): BObject<R>
+  /**
+   * Create a new object schema with `key` added or replaced.
+   * With the `from` option the key is read from another key of the input value and renamed.
+   */
+  key<K extends string, T>(key: K, schema: BValue<T>, options?: KeyOptions): BObject<(Omit<Exclude<R, null | undefined>, K> & { [key in K]: T }) | Extract<R, null | undefined>>
+  /**
+   * Convert `snake_case` or `kebab-case` keys of the input value to camelCase when parsing.
+   * Keys with an alias set by `key()` are renamed to their alias instead.
+   */
+  camelCaseKeys(from: 'snake_case' | 'kebab-case'): BObject<R>
+  /** Require the values of 2 keys to be strictly equal, e.g. a password and its confirmation. */
+  equalFields(a: Extract<keyof Exclude<R, null | undefined>, string>, b: Extract<keyof Exclude<R, null | undefined>, string>): BObject<R>
+  /**
+   * Require the number or Date of key `a` to be less than the one of key `b`.
+   * Passes when either key is missing.
+   */
+  lessThan(a: Extract<keyof Exclude<R, null | undefined>, string>, b: Extract<keyof Exclude<R, null | undefined>, string>): BObject<R>
+  /** Require `key` when all keys of `conditions` strictly equal their values. */
+  requiredIf(key: Extract<keyof Exclude<R, null | undefined>, string>, conditions: Partial<Exclude<R, null | undefined>>): BObject<R>
+  /** Allow at most one of the keys to be present, null values count as missing. */
+  mutuallyExclusive(keys: Extract<keyof Exclude<R, null | undefined>, string>[]): BObject<R>
+  /** Require at least one of the keys to be present, null values count as missing. */
+  atLeastOneOf(keys: Extract<keyof Exclude<R, null | undefined>, string>[]): BObject<R>
+  /** Freeze the parsed value with `Object.freeze`, nested objects and arrays as well when `deep`. */
+  readonly<D extends boolean = false>(deep?: D): BObject<(D extends true ? DeepReadonly<Exclude<R, null | undefined>> : Readonly<Exclude<R, null | undefined>>) | Extract<R, null | undefined>>
+  /**
+   * Create a new object schema with only the given keys.
+   * Optional and Nullable parameters of the object are ignored for new object.
+   */
+  pick<K extends keyof Exclude<R, null | undefined>>(keys: K[]): BObject<Pick<Exclude<R, null | undefined>, K>>
+  /**
+   * Create a new object schema without the given keys.
+   * Optional and Nullable parameters of the object are ignored for new object.
+   */
+  omit<K extends keyof Exclude<R, null | undefined>>(keys: K[]): BObject<Omit<Exclude<R, null | undefined>, K>>
+  /**
+   * Create a new object schema with the given keys, or all keys when omitted, made optional.
+   * Optional and Nullable parameters of the object are ignored for new object.
+   */
+  partial<K extends keyof Exclude<R, null | undefined> = keyof Exclude<R, null | undefined>>(keys?: K[]): BObject<Omit<Exclude<R, null | undefined>, K> & Partial<Pick<Exclude<R, null | undefined>, K>>>
+  /**
+   * Create a new object schema with all keys optional, including keys of nested objects.
+   * Optional and Nullable parameters of the object are ignored for new object.
+   */
+  deepPartial(): BObject<DeepPartial<Exclude<R, null | undefined>>>
+  /**
+   * Create a new object schema with the given keys, or all keys when omitted, made required.
+   * Optional and Nullable parameters of the object are ignored for new object.
+   */
+  requiredKeys<K extends keyof Exclude<R, null | undefined> = keyof Exclude<R, null | undefined>>(keys?: K[]): BObject<Omit<Exclude<R, null | undefined>, K> & Required<Pick<Exclude<R, null | undefined>, K>>>
+  /**
+   * Create a new object schema with keys added, existing keys are overwritten.
+   * Optional and Nullable parameters of the object are ignored for new object.
+   */
+  extend<T extends Record<string, BValue>>(shape: T): BObject<Omit<Exclude<R, null | undefined>, keyof T> & InferBObjectParseType<T>>
+  /** Create an enum schema of the object keys. */
+  keyof(): BEnum<Extract<keyof Exclude<R, null | undefined>, string>>
 }
 export class BString<R> {
   optional(): BString<R | undefined>
@@ -114,17 +736,75 @@ export class BString<R> {
   nullish(): BString<R | null | undefined>
   required(): BString<Exclude<R, undefined>>
   nonNullable(): BString<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BString<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BString<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BString<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   static default(): BString<string>
-  parse(value: unknown): R
   length(value: number): BString
   min(value: number): BString
   max(value: number): BString
+  /** Require the string to match a regular expression, which is not anchored by default. */
+  pattern(pattern: string): BString<R>
+  /** Require the string to be in a named format, like the `format` keyword of JSON Schema. */
+  format(format: 'date-time' | 'date' | 'time' | 'email' | 'hostname' | 'ipv4' | 'ipv6' | 'uri' | 'uuid'): BString<R>
 }
 export class BTuple<R> {
   optional(): BTuple<R | undefined>
@@ -132,16 +812,82 @@ export class BTuple<R> {
   nullish(): BTuple<R | null | undefined>
   required(): BTuple<Exclude<R, undefined>>
   nonNullable(): BTuple<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BTuple<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BTuple<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BTuple<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   /** For internal use only! */
   static _fromWrapped(wrapped: Array<BWrapped>): unknown
-  parse(value: unknown): R
-  parse2(value: unknown): R
+  /** For internal use only! */
+  static _fromWrappedNamed(names: Array<string>, wrapped: Array<BWrapped>): unknown
+  /** Freeze the parsed value with `Object.freeze`, nested objects and arrays as well when `deep`. */
+  readonly<D extends boolean = false>(deep?: D): BTuple<(D extends true ? DeepReadonly<Exclude<R, null | undefined>> : Readonly<Exclude<R, null | undefined>>) | Extract<R, null | undefined>>
+  /** Allow any amount of elements after the tuple elements, parsed with `schema`. */
+  rest<T>(schema: BValue<T>): BTuple<(Exclude<R, null | undefined> extends readonly unknown[] ? [...Exclude<R, null | undefined>, ...T[]] : never) | Extract<R, null | undefined>>
+  /**
+   * How holes of sparse arrays are handled, by default they are parsed as undefined.
+   * Compacting removes holes in place, moving later elements to lower indices.
+   */
+  holes(policy: 'undefined' | 'reject' | 'compact'): BTuple<R>
+  /** Accept `arguments` objects and typed arrays, parsing converts them to arrays. */
+  arrayLike(): BTuple<R>
 }
 export class BUndefined<R> {
   optional(): BUndefined<R | undefined>
@@ -149,14 +895,68 @@ export class BUndefined<R> {
   nullish(): BUndefined<R | null | undefined>
   required(): BUndefined<Exclude<R, undefined>>
   nonNullable(): BUndefined<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BUndefined<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BUndefined<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BUndefined<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   static default(): BUndefined<undefined>
-  parse(value: unknown): R
 }
 export class BUnion<R> {
   optional(): BUnion<R | undefined>
@@ -164,15 +964,78 @@ export class BUnion<R> {
   nullish(): BUnion<R | null | undefined>
   required(): BUnion<Exclude<R, undefined>>
   nonNullable(): BUnion<Exclude<R, null>>
+  /**
+   * Brand the output type, making it incompatible with structurally identical types.
+   * The brand name is used in errors, e.g. `Expected UserId: ...`.
+   */
+  brand<B extends string>(name: B): BUnion<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>
+  /** Set a human readable description of the value. */
+  describe(description: string): this
+  title(title: string): this
+  examples(examples: R[]): this
+  /** Mark the value as deprecated, `parseSafe()` returns a warning when the value is present. */
+  deprecated(reason?: string | undefined | null): this
+  /** Set custom metadata, replacing previously set custom metadata. */
+  meta(meta: Record<string, unknown>): this
+  /** Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`. */
+  get metadata(): SchemaMetadata<R>
+  /** Plain JSON-serializable description of the schema and all nested schemas. */
+  toDefinition(): SchemaDefinition
+  /**
+   * JSON Schema document describing the values accepted by the schema.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJSONSchema(options?: JsonSchemaOptions | undefined | null): Record<string, unknown>
+  /**
+   * JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+   * Constraints without a JTD keyword, like lengths and ranges, are rejected.
+   * Custom checks, effects and rules between keys are not included.
+   */
+  toJTD(): Record<string, unknown>
+  /** Brand name set by `brand()`. */
+  get brandName(): string | null
   /**
    * For internal use only!
    * Create BWrapped instance of this value to be used by other methods
    */
   _toWrapped(): BWrapped
+  /**
+   * Add a custom check, run after the native checks passed.
+   * Parsing fails with `message` when `check` returns a falsy value.
+   */
+  refine(check: (value: NonNullable<R>) => unknown, message?: string): this
+  /** Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`. */
+  superRefine(refinement: (value: NonNullable<R>, ctx: RefinementContext) => void): this
+  /** Map the value to a new value after the native checks and previous effects passed. */
+  transform<T>(transform: (value: NonNullable<R>) => T): BUnion<T | Extract<R, null | undefined>>
+  /**
+   * For internal use only!
+   * Run a function on the raw value before parsing, use `preprocess()` instead.
+   */
+  _preprocess(preprocess: (value: unknown) => unknown): this
+  /** Parse the output of this schema with another schema. */
+  pipe<T>(schema: BValue<T>): BUnion<T | Extract<R, null | undefined>>
+  parse(value: unknown): R
   parseSafe(value: unknown): ParseSafe<R>
+  /**
+   * Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+   * Unknown object keys are never stripped and values are not mutated.
+   */
+  is(value: unknown): value is R
+  /** Parse allowing async refinements and transforms, independent async steps run concurrently. */
+  parseAsync(value: unknown): Promise<R>
+  parseSafeAsync(value: unknown): Promise<ParseSafe<R>>
   /** For internal use only! */
   static _fromWrapped(wrapped: Array<BWrapped>): unknown
-  parse(value: unknown): R
+  /** Require values to match exactly one of the types, like `oneOf` of JSON Schema. */
+  exclusive(): BUnion<R>
   merge<T>(schema: BUnion<T>): BUnion<R | T>
 }
 export class BWrapped { }
+/**
+ * Passed to `superRefine` callbacks to report issues.
+ * Parsing fails with the first reported issue.
+ */
+export class RefinementContext {
+  addIssue(issue: Issue): void
+}
@@ -295,10 +295,13 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { BArray, BBoolean, BNull, BNumber, BObject, BString, BTuple, BUndefined, BUnion, BWrapped } = nativeBinding
+const { BArray, BBoolean, BConditional, BEnum, BInstance, BNull, BNumber, BObject, BString, BTuple, BUndefined, BUnion, BWrapped, RefinementContext, fromDefinition, fromJSONSchema, fromJTD, toOpenAPIComponents } = nativeBinding
 
 module.exports.BArray = BArray
 module.exports.BBoolean = BBoolean
+module.exports.BConditional = BConditional
+module.exports.BEnum = BEnum
+module.exports.BInstance = BInstance
 module.exports.BNull = BNull
 module.exports.BNumber = BNumber
 module.exports.BObject = BObject
@@ -307,3 +310,8 @@ module.exports.BTuple = BTuple
 module.exports.BUndefined = BUndefined
 module.exports.BUnion = BUnion
 module.exports.BWrapped = BWrapped
+module.exports.RefinementContext = RefinementContext
+module.exports.fromDefinition = fromDefinition
+module.exports.fromJSONSchema = fromJSONSchema
+module.exports.fromJTD = fromJTD
+module.exports.toOpenAPIComponents = toOpenAPIComponents
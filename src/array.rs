@@ -1,8 +1,19 @@
-use napi::{bindgen_prelude::ClassInstance, Env, JsObject, JsUnknown, Result};
+use std::{cell::Cell, collections::HashSet, rc::Rc};
+
+use napi::{bindgen_prelude::ClassInstance, Env, JsObject, JsUnknown, Status, ValueType};
 
 use crate::{
-  common::{invalid_arg, parse_btype},
-  impl_base_methods, impl_parse_safe_method_with_env, BType, BValueBase, BWrapped,
+  common::{
+    check_btype, invalid_arg, parse_btype, transforms_value, unwrap_schema, with_path,
+    ArrayOptions, ClonableJsUnknown, Freeze, PersistentValue,
+  },
+  context::{is_invalid, rethrow, then_catch},
+  definition::{btype_definition, check_range, Definition, DefinitionReader},
+  error::Result,
+  impl_base_methods, impl_parse_methods,
+  json_schema::{Draft, JsonSchemaWriter},
+  jtd::JtdWriter,
+  BType, BValueBase, BWrapped,
 };
 
 #[napi]
@@ -13,6 +24,43 @@ pub struct BArray {
   len: Option<u32>,
   min: Option<u32>,
   max: Option<u32>,
+  /// Key path of the value elements have to be unique by, empty for the elements themselves.
+  unique: Option<Vec<String>>,
+  contains: Option<Contains>,
+  array: ArrayOptions,
+  readonly: Option<Freeze>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Contains {
+  b_type: Box<BType>,
+  min: u32,
+  max: Option<u32>,
+}
+
+#[napi(object)]
+pub struct ContainsOptions {
+  /// Minimum amount of matching elements, defaults to 1.
+  pub min: Option<u32>,
+  pub max: Option<u32>,
+}
+
+impl Contains {
+  /// Fail when the amount of elements matching the type is out of range.
+  fn check(&self, count: u32) -> Result<()> {
+    let expected = match self.max {
+      Some(max) if count > max => format!("at most {}", max),
+      _ if count < self.min => format!("at least {}", self.min),
+      _ => return Ok(()),
+    };
+    Err(invalid_arg(
+      format!(
+        "Array contains {} matching element(s), expected {}",
+        count, expected
+      )
+      .as_str(),
+    ))
+  }
 }
 
 impl_base_methods!(
@@ -22,9 +70,11 @@ impl_base_methods!(
   "BArray<R | null>",
   "BArray<R | null | undefined>",
   "BArray<Exclude<R, undefined>>",
-  "BArray<Exclude<R, null>>"
+  "BArray<Exclude<R, null>>",
+  "BArray<T | Extract<R, null | undefined>>",
+  "BArray<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method_with_env!(BArray);
+impl_parse_methods!(BArray);
 
 #[napi]
 impl BArray {
@@ -37,17 +87,141 @@ impl BArray {
       len: None,
       min: None,
       max: None,
+      unique: None,
+      contains: None,
+      array: ArrayOptions::default(),
+      readonly: None,
+    }
+  }
+
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "array", &self.base)?;
+    definition.set_schema("element", Some(&self.inner))?;
+    definition.set_u32("length", self.len)?;
+    definition.set_u32("min", self.min)?;
+    definition.set_u32("max", self.max)?;
+    match self.unique.as_deref() {
+      Some([]) => definition.set("unique", env.get_boolean(true)?)?,
+      Some(path) => definition.set_str("unique", Some(&path.join(".")))?,
+      None => {}
     }
+    if let Some(contains) = &self.contains {
+      let mut obj = env.create_object()?;
+      obj.set_named_property("schema", btype_definition(env, &contains.b_type)?)?;
+      obj.set_named_property("min", env.create_uint32(contains.min)?)?;
+      if let Some(max) = contains.max {
+        obj.set_named_property("max", env.create_uint32(max)?)?;
+      }
+      definition.set("contains", obj)?;
+    }
+    definition.set_array_options(&self.array)?;
+    definition.set_readonly(self.readonly)?;
+    Ok(definition.into_object())
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, env: Env, value: JsUnknown) -> Result<JsUnknown> {
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    let (min, max) = (reader.u32("min")?, reader.u32("max")?);
+    check_range("min", min, "max", max)?;
+
+    let unique = match reader.get("unique")? {
+      Some(value) => match value.get_type()? {
+        ValueType::Boolean => value.coerce_to_bool()?.get_value()?.then(Vec::new),
+        ValueType::String => Some(
+          value
+            .coerce_to_string()?
+            .into_utf8()?
+            .as_str()?
+            .split('.')
+            .map(String::from)
+            .collect(),
+        ),
+        value_type => {
+          return Err(with_path(
+            invalid_arg(format!("expect Boolean or String, got: {}", value_type).as_str()),
+            "unique",
+          ))
+        }
+      },
+      None => None,
+    };
+
+    let contains = reader.nested("contains", |contains| {
+      let (min, max) = (contains.u32("min")?.unwrap_or(1), contains.u32("max")?);
+      check_range("min", Some(min), "max", max)?;
+      Ok(Contains {
+        b_type: Box::new(contains.required_schema("schema")?),
+        min,
+        max,
+      })
+    })?;
+
+    Ok(BArray {
+      base,
+      inner: Box::new(reader.required_schema("element")?),
+      len: reader.u32("length")?,
+      min,
+      max,
+      unique,
+      contains,
+      array: reader.array_options()?,
+      readonly: reader.readonly()?,
+    })
+  }
+
+  pub fn jtd(&self, writer: &mut JtdWriter) -> Result<JsObject> {
+    if self.len.is_some() || self.min.is_some() || self.max.is_some() {
+      return Err(invalid_arg("Array lengths can not be represented in JTD"));
+    }
+    if self.unique.is_some() {
+      return Err(invalid_arg("Unique elements can not be represented in JTD"));
+    }
+    if self.contains.is_some() {
+      return Err(invalid_arg(
+        "Contains constraints can not be represented in JTD",
+      ));
+    }
+    let mut obj = writer.env().create_object()?;
+    let elements = writer
+      .schema(&self.inner)
+      .map_err(|err| with_path(err, "elements"))?;
+    obj.set_named_property("elements", elements)?;
+    Ok(obj)
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let mut obj = writer.typed("array")?;
+    let items = writer
+      .schema(&self.inner)
+      .map_err(|err| with_path(err, "items"))?;
+    obj.set_named_property("items", items)?;
+    writer.set_u32(&mut obj, "minItems", self.len.or(self.min))?;
+    writer.set_u32(&mut obj, "maxItems", self.len.or(self.max))?;
+    if self.unique.as_deref() == Some(&[]) {
+      obj.set_named_property("uniqueItems", writer.env().get_boolean(true)?)?;
+    }
+    if let Some(contains) = &self.contains {
+      let schema = writer
+        .schema(&contains.b_type)
+        .map_err(|err| with_path(err, "contains"))?;
+      obj.set_named_property("contains", schema)?;
+      if writer.draft() == Draft::Draft2020 {
+        writer.set_u32(&mut obj, "minContains", Some(contains.min))?;
+        writer.set_u32(&mut obj, "maxContains", contains.max)?;
+      }
+    }
+    Ok(obj)
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
+    let pending = ctx.pending_len();
 
-    let obj: JsObject = value.try_into()?;
-    let length = obj.get_array_length()?;
+    let mut obj = ctx.writable(self.array.expect_array(ctx.env, value)?)?;
+    let length = obj.get_array_length_unchecked()?;
+    let length = self.array.fill_holes(ctx.env, &mut obj, length)?;
 
     if let Some(expected) = self.len {
       if expected != length {
@@ -72,12 +246,189 @@ impl BArray {
       }
     }
 
+    let transforms = ctx.copies() || transforms_value(&self.inner);
+    for i in 0..length {
+      let el = obj.get_element_unchecked::<JsUnknown>(i)?;
+      let segment = i.to_string();
+      let parsed = ctx.nested(&segment, |ctx| parse_btype(&self.inner, el, ctx));
+      let parsed = parsed.map_err(|err| with_path(err, &segment))?;
+      if ctx.is_async() && parsed.is_promise()? {
+        ctx.defer_write(&obj, &segment, parsed, move |obj, value| {
+          Ok(obj.set_element(i, value)?)
+        })?;
+      } else if transforms {
+        obj.set_element(i, parsed)?;
+      }
+    }
+
+    if ctx.pending_len() > pending {
+      // elements are compared once the async steps wrote their parsed values
+      let (array, fork) = (self.clone(), ctx.fork());
+      let target = PersistentValue::new(ctx.env, &obj)?;
+      ctx.defer_after(pending, move |env| {
+        let mut ctx = fork.fork();
+        let result = array
+          .check_elements(&mut ctx, &target.get(env)?, length)
+          .and_then(|_| Ok(env.get_undefined()?.into_unknown()));
+        Ok(ctx.settle(result)?.into_unknown())
+      })?;
+    } else {
+      self.check_elements(ctx, &obj, length)?;
+    }
+
+    let parsed = self.base.apply_effects(ctx, obj.into_unknown())?;
+    match self.readonly {
+      Some(freeze) => freeze.apply(ctx, pending, parsed),
+      None => Ok(parsed),
+    }
+  }
+
+  /// Check the parsed elements for duplicates and the amount matching `contains`,
+  /// which is counted once async steps of the `contains` type resolved.
+  fn check_elements(&self, ctx: &mut ParseContext, obj: &JsObject, length: u32) -> Result<()> {
+    if let Some(i) = self.find_duplicate(ctx.env, obj, length)? {
+      let message = match self.unique.as_deref() {
+        Some([]) | None => "Array contains duplicate elements".to_string(),
+        Some(path) => format!("Array contains duplicate values for '{}'", path.join(".")),
+      };
+      return Err(with_path(invalid_arg(&message), &i.to_string()));
+    }
+
+    let Some(contains) = &self.contains else {
+      return Ok(());
+    };
+    let matched = Rc::new(Cell::new(0));
+    let pending = ctx.pending_len();
     for i in 0..length {
       let el = obj.get_element_unchecked::<JsUnknown>(i)?;
-      parse_btype(&self.inner, el, env)?;
+      // like union members, elements are parsed into copies and their warnings are dropped
+      let mut member = ctx.fork_member();
+      match parse_btype(&contains.b_type, el, &mut member) {
+        Ok(parsed) if member.pending_len() > 0 || (member.is_async() && parsed.is_promise()?) => {
+          let settled = member.settle(Ok(parsed))?;
+          let counter = matched.clone();
+          let counted = then_catch(
+            ctx.env,
+            settled,
+            move |env, _| {
+              counter.set(counter.get() + 1);
+              Ok(env.get_undefined()?.into_unknown())
+            },
+            |env, reason| match is_invalid(&reason)? {
+              true => Ok(env.get_undefined()?.into_unknown()),
+              false => rethrow(env, reason),
+            },
+          )?;
+          ctx.defer(counted);
+        }
+        Ok(_) => matched.set(matched.get() + 1),
+        Err(err) if err.status == Status::InvalidArg => member.discard()?,
+        Err(err) => {
+          member.discard()?;
+          return Err(err);
+        }
+      }
+    }
+
+    if ctx.pending_len() > pending {
+      let contains = contains.clone();
+      return ctx.defer_after(pending, move |env| {
+        contains.check(matched.get())?;
+        Ok(env.get_undefined()?.into_unknown())
+      });
     }
+    contains.check(matched.get())
+  }
 
-    Ok(obj.into_unknown())
+  /// Index of the first element equal to a previous element.
+  fn find_duplicate(&self, env: Env, obj: &JsObject, length: u32) -> Result<Option<u32>> {
+    if let Some(path) = &self.unique {
+      let mut seen = Seen::default();
+      for i in 0..length {
+        let el = obj.get_element_unchecked::<JsUnknown>(i)?;
+        if !seen.insert(env, get_path(env, el, path)?)? {
+          return Ok(Some(i));
+        }
+      }
+    }
+    Ok(None)
+  }
+
+  /// Whether the elements as a whole are invalid, like `check_elements` when checking.
+  fn violates(&self, env: Env, obj: &JsObject, length: u32) -> Result<bool> {
+    if self.find_duplicate(env, obj, length)?.is_some() {
+      return Ok(true);
+    }
+
+    if let Some(contains) = &self.contains {
+      let mut count = 0;
+      for i in 0..length {
+        let el = obj.get_element_unchecked::<JsUnknown>(i)?;
+        if check_btype(&contains.b_type, el, env)?.is_some() {
+          count += 1;
+        }
+      }
+      return Ok(contains.check(count).is_err());
+    }
+
+    Ok(false)
+  }
+
+  /// Freeze the parsed value with `Object.freeze`, nested objects and arrays as well when `deep`.
+  #[napi(
+    ts_args_type = "deep?: D",
+    ts_return_type = "BArray<(D extends true ? DeepReadonly<Exclude<R, null | undefined>> : Readonly<Exclude<R, null | undefined>>) | Extract<R, null | undefined>>"
+  )]
+  pub fn readonly(&self, deep: Option<bool>) -> Self {
+    BArray {
+      readonly: Some(match deep {
+        Some(true) => Freeze::Deep,
+        _ => Freeze::Shallow,
+      }),
+      ..self.clone()
+    }
+  }
+
+  /// Create a copy with the element type mapped by `f`.
+  pub fn map_inner(&self, f: impl Fn(&BType) -> BType) -> BArray {
+    BArray {
+      inner: Box::new(f(&self.inner)),
+      ..self.clone()
+    }
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    let Some(obj) = self.array.as_array(env, value)? else {
+      return Ok(None);
+    };
+    let Some((obj, length)) = self.array.check_holes(env, obj)? else {
+      return Ok(None);
+    };
+
+    let valid = match self.len {
+      Some(expected) => expected == length,
+      None => self.min.is_none_or(|min| length >= min) && self.max.is_none_or(|max| length <= max),
+    };
+    if !valid {
+      return Ok(None);
+    }
+
+    for i in 0..length {
+      let el = obj.get_element_unchecked::<JsUnknown>(i)?;
+      if check_btype(&self.inner, el, env)?.is_none() {
+        return Ok(None);
+      }
+    }
+    if self.violates(env, &obj, length)? {
+      return Ok(None);
+    }
+
+    self.base.check_effects(env, obj.into_unknown())
   }
 
   #[napi(ts_return_type = "BArray<R>")]
@@ -103,4 +454,153 @@ impl BArray {
       ..self.clone()
     }
   }
+
+  /// How holes of sparse arrays are handled, by default they are parsed as undefined.
+  /// Compacting removes holes in place, moving later elements to lower indices.
+  #[napi(
+    ts_args_type = "policy: 'undefined' | 'reject' | 'compact'",
+    ts_return_type = "BArray<R>"
+  )]
+  pub fn holes(&self, policy: String) -> Result<Self> {
+    Ok(BArray {
+      array: self.array.with_holes(&policy)?,
+      ..self.clone()
+    })
+  }
+
+  /// Accept `arguments` objects and typed arrays, parsing converts them to arrays.
+  #[napi(ts_return_type = "BArray<R>")]
+  pub fn array_like(&self) -> Self {
+    BArray {
+      array: ArrayOptions {
+        array_like: true,
+        ..self.array
+      },
+      ..self.clone()
+    }
+  }
+
+  pub fn converts(&self) -> bool {
+    self.array.converts()
+  }
+
+  /// Require at least 1 element.
+  #[napi(
+    ts_return_type = "BArray<[Exclude<R, null | undefined>[number], ...Exclude<R, null | undefined>[number][]] | Extract<R, null | undefined>>"
+  )]
+  pub fn nonempty(&self) -> Self {
+    BArray {
+      min: Some(self.min.unwrap_or(0).max(1)),
+      ..self.clone()
+    }
+  }
+
+  /// Require elements to be unique, primitives are compared by value and objects by identity.
+  #[napi(ts_return_type = "BArray<R>")]
+  pub fn unique(&self) -> Self {
+    BArray {
+      unique: Some(vec![]),
+      ..self.clone()
+    }
+  }
+
+  /// Require the values at `keyPath` of the elements to be unique, e.g. `'id'` or `'item.id'`.
+  #[napi(ts_return_type = "BArray<R>")]
+  pub fn unique_by(&self, key_path: String) -> Self {
+    BArray {
+      unique: Some(key_path.split('.').map(String::from).collect()),
+      ..self.clone()
+    }
+  }
+
+  /// Require the amount of elements valid for `schema` to be within `min` (default 1) and `max`.
+  #[napi(
+    ts_args_type = "schema: BValue, options?: ContainsOptions",
+    ts_return_type = "BArray<R>"
+  )]
+  pub fn contains(
+    &self,
+    env: Env,
+    schema: JsObject,
+    options: Option<ContainsOptions>,
+  ) -> Result<Self> {
+    let (min, max) = options.map_or((None, None), |options| (options.min, options.max));
+    let contains = Contains {
+      b_type: Box::new(unwrap_schema(env, schema)?),
+      min: min.unwrap_or(1),
+      max,
+    };
+    if contains.max.is_some_and(|max| max < contains.min) {
+      return Err(invalid_arg("Contains maximum is lower than minimum"));
+    }
+
+    Ok(BArray {
+      contains: Some(contains),
+      ..self.clone()
+    })
+  }
+}
+
+/// Get the value at a key path, undefined when a key is missing.
+fn get_path(env: Env, value: JsUnknown, path: &[String]) -> Result<JsUnknown> {
+  let mut value = value;
+  for key in path {
+    if value.get_type()? != ValueType::Object {
+      return Ok(env.get_undefined()?.into_unknown());
+    }
+    value = value
+      .coerce_to_object()?
+      .get_named_property_unchecked::<JsUnknown>(key)?;
+  }
+  Ok(value)
+}
+
+/// Primitive values hashed by value, SameValueZero semantics like a JS `Set`.
+#[derive(Hash, PartialEq, Eq)]
+enum HashKey {
+  Undefined,
+  Null,
+  Boolean(bool),
+  Number(u64),
+  String(String),
+}
+
+/// Values seen while checking uniqueness, other values (e.g. objects) are compared with `===`.
+#[derive(Default)]
+struct Seen {
+  primitives: HashSet<HashKey>,
+  objects: Vec<ClonableJsUnknown>,
+}
+
+impl Seen {
+  /// Add a value, returns false when an equal value was already seen.
+  fn insert(&mut self, env: Env, value: JsUnknown) -> Result<bool> {
+    let key = match value.get_type()? {
+      ValueType::Undefined => HashKey::Undefined,
+      ValueType::Null => HashKey::Null,
+      ValueType::Boolean => HashKey::Boolean(value.coerce_to_bool()?.get_value()?),
+      ValueType::Number => {
+        let number = value.coerce_to_number()?.get_double()?;
+        // -0 equals 0 and NaN equals NaN
+        let number = match number {
+          0.0 => 0.0,
+          n if n.is_nan() => f64::NAN,
+          n => n,
+        };
+        HashKey::Number(number.to_bits())
+      }
+      ValueType::String => HashKey::String(value.coerce_to_string()?.into_utf8()?.into_owned()?),
+      _ => {
+        let value = ClonableJsUnknown::new(env, value)?;
+        for seen in &self.objects {
+          if env.strict_equals(seen.create_clone()?, value.create_clone()?)? {
+            return Ok(false);
+          }
+        }
+        self.objects.push(value);
+        return Ok(true);
+      }
+    };
+    Ok(self.primitives.insert(key))
+  }
 }
@@ -1,6 +1,14 @@
-use napi::{JsBoolean, JsUnknown, Result};
+use napi::{Env, JsBoolean, JsObject, JsUnknown, ValueType};
 
-use crate::{common::invalid_arg, impl_base_methods, impl_parse_safe_method, BValueBase};
+use crate::{
+  common::invalid_arg,
+  definition::{Definition, DefinitionReader},
+  error::Result,
+  impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter,
+  jtd::JtdWriter,
+  BValueBase,
+};
 
 #[napi]
 #[derive(Debug, Clone, PartialEq)]
@@ -16,9 +24,11 @@ impl_base_methods!(
   "BBoolean<R | null>",
   "BBoolean<R | null | undefined>",
   "BBoolean<Exclude<R, undefined>>",
-  "BBoolean<Exclude<R, null>>"
+  "BBoolean<Exclude<R, null>>",
+  "BBoolean<T | Extract<R, null | undefined>>",
+  "BBoolean<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method!(BBoolean);
+impl_parse_methods!(BBoolean);
 
 #[napi]
 impl BBoolean {
@@ -30,8 +40,40 @@ impl BBoolean {
     }
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, value: JsUnknown) -> Result<JsUnknown> {
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "boolean", &self.base)?;
+    if let Some(expected) = self.expected {
+      definition.set("value", definition.env().get_boolean(expected)?)?;
+    }
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    Ok(BBoolean {
+      base,
+      expected: reader.bool("value")?,
+    })
+  }
+
+  pub fn jtd(&self, writer: &mut JtdWriter) -> Result<JsObject> {
+    if self.expected.is_some() {
+      return Err(invalid_arg(
+        "Boolean constants can not be represented in JTD",
+      ));
+    }
+    writer.typed("boolean")
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let mut obj = writer.typed("boolean")?;
+    if let Some(expected) = self.expected {
+      obj.set_named_property("const", writer.env().get_boolean(expected)?)?;
+    }
+    Ok(obj)
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
@@ -47,7 +89,27 @@ impl BBoolean {
       }
     }
 
-    Ok(js_bool.into_unknown())
+    self.base.apply_effects(ctx, js_bool.into_unknown())
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    if value.get_type()? != ValueType::Boolean {
+      return Ok(None);
+    }
+    let js_bool = unsafe { value.cast::<JsBoolean>() };
+
+    if let Some(expected) = self.expected {
+      if expected != js_bool.get_value()? {
+        return Ok(None);
+      }
+    }
+
+    self.base.check_effects(env, js_bool.into_unknown())
   }
 
   #[napi(ts_return_type = "BBoolean<R>")]
@@ -1,9 +1,19 @@
+use std::{fmt, rc::Rc};
+
+use regex::Regex;
+
 use napi::{
-  bindgen_prelude::ToNapiValue, sys::napi_env__, sys::napi_value__, Env, Error, JsUnknown,
-  NapiValue, Result, Status,
+  bindgen_prelude::{ClassInstance, FromNapiValue, ToNapiValue},
+  sys::napi_env__,
+  sys::napi_value__,
+  Env, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue, Ref, Status, ValueType,
 };
 
-use crate::{BType, BValueBase};
+use crate::{
+  context::{then, ParseContext},
+  error::{Error, Result},
+  BType, BValueBase, BWrapped,
+};
 
 pub struct ClonableJsUnknown {
   napi_env: *mut napi_env__,
@@ -25,18 +35,330 @@ impl ClonableJsUnknown {
   }
 }
 
-pub fn parse_btype(b_type: &BType, value: JsUnknown, env: Env) -> Result<JsUnknown> {
-  match &*b_type {
-    BType::Undefined(s) => s.parse(value),
-    BType::Null(s) => s.parse(value),
-    BType::Boolean(s) => s.parse(value),
-    BType::Number(s) => s.parse(value),
-    BType::String(s) => s.parse(value),
-    BType::Array(s) => s.parse(env, value),
-    BType::Object(s) => s.parse(env, value),
-    BType::Union(s) => s.parse(env, value),
-    BType::Tuple(s) => s.parse(env, value),
+/// Persistent reference to a JS value, which outlives the current call and is shared between clones.
+/// The reference is released once the last clone is dropped.
+#[derive(Clone)]
+pub struct PersistentValue {
+  inner: Rc<ValueRef>,
+}
+
+struct ValueRef {
+  napi_env: *mut napi_env__,
+  reference: Ref<()>,
+  /// References can only be created to objects, primitives are stored in a wrapping object.
+  boxed: bool,
+}
+
+impl Drop for ValueRef {
+  fn drop(&mut self) {
+    let env = unsafe { Env::from_raw(self.napi_env) };
+    let _ = self.reference.unref(env);
+  }
+}
+
+impl PersistentValue {
+  pub fn new<T: NapiRaw>(env: Env, value: T) -> Result<Self> {
+    let value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), value.raw()) };
+    let boxed = !matches!(value.get_type()?, ValueType::Object | ValueType::Function);
+
+    let reference = match boxed {
+      true => {
+        let mut wrapper = env.create_object()?;
+        wrapper.set_named_property("value", value)?;
+        env.create_reference(wrapper)?
+      }
+      false => env.create_reference(value)?,
+    };
+
+    Ok(PersistentValue {
+      inner: Rc::new(ValueRef {
+        napi_env: env.raw(),
+        reference,
+        boxed,
+      }),
+    })
+  }
+
+  pub fn get<T: NapiValue>(&self, env: Env) -> Result<T> {
+    match self.inner.boxed {
+      true => Ok(
+        env
+          .get_reference_value::<JsObject>(&self.inner.reference)?
+          .get_named_property_unchecked("value")?,
+      ),
+      false => Ok(env.get_reference_value_unchecked(&self.inner.reference)?),
+    }
+  }
+}
+
+impl fmt::Debug for PersistentValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("PersistentValue")
+  }
+}
+
+impl PartialEq for PersistentValue {
+  fn eq(&self, other: &Self) -> bool {
+    Rc::ptr_eq(&self.inner, &other.inner)
+  }
+}
+
+/// Compiled regex, compared by its source.
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+  pub fn new(source: &str) -> Result<Self> {
+    Regex::new(source)
+      .map(Pattern)
+      .map_err(|err| invalid_arg(format!("Invalid regex '{}': {}", source, err).as_str()))
+  }
+
+  pub fn is_match(&self, value: &str) -> bool {
+    self.0.is_match(value)
+  }
+
+  pub fn as_str(&self) -> &str {
+    self.0.as_str()
+  }
+}
+
+impl PartialEq for Pattern {
+  fn eq(&self, other: &Self) -> bool {
+    self.as_str() == other.as_str()
+  }
+}
+
+/// How holes of sparse arrays are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Holes {
+  /// Holes are parsed as undefined elements.
+  #[default]
+  Undefined,
+  Reject,
+  /// Holes are removed, later elements move to lower indices.
+  Compact,
+}
+
+/// Options for reading array values, shared by arrays and tuples.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ArrayOptions {
+  pub holes: Holes,
+  /// Accept `arguments` objects and typed arrays, which are converted to arrays.
+  pub array_like: bool,
+}
+
+impl ArrayOptions {
+  pub fn with_holes(self, policy: &str) -> Result<Self> {
+    let holes = match policy {
+      "undefined" => Holes::Undefined,
+      "reject" => Holes::Reject,
+      "compact" => Holes::Compact,
+      _ => {
+        return Err(invalid_arg(
+          format!("Unknown hole policy '{}'", policy).as_str(),
+        ))
+      }
+    };
+    Ok(ArrayOptions { holes, ..self })
+  }
+
+  /// Whether parsing can return a different array than it received.
+  pub fn converts(&self) -> bool {
+    self.array_like
+  }
+
+  /// Get a value as array, accepted array-likes are converted to a new array.
+  pub fn as_array(&self, env: Env, value: JsUnknown) -> Result<Option<JsObject>> {
+    if value.is_array()? {
+      return Ok(Some(unsafe { value.cast::<JsObject>() }));
+    }
+
+    let array_like = self.array_like
+      && value.get_type()? == ValueType::Object
+      && (value.is_typedarray()? || is_arguments(env, &value)?);
+    if !array_like {
+      return Ok(None);
+    }
+
+    let array: JsObject = env.get_global()?.get_named_property_unchecked("Array")?;
+    let from = array.get_named_property_unchecked::<JsFunction>("from")?;
+    Ok(Some(from.call(Some(&array), &[value])?.coerce_to_object()?))
+  }
+
+  /// Like `as_array`, but fails for values which are not accepted.
+  pub fn expect_array(&self, env: Env, value: JsUnknown) -> Result<JsObject> {
+    let value_type = value.get_type()?;
+    match self.as_array(env, value)? {
+      Some(array) => Ok(array),
+      None => Err(invalid_arg(
+        format!("expect Array, got: {}", value_type).as_str(),
+      )),
+    }
+  }
+
+  /// Apply the hole policy to an array in place, returns the new length.
+  pub fn fill_holes(&self, env: Env, array: &mut JsObject, length: u32) -> Result<u32> {
+    if self.holes == Holes::Undefined {
+      return Ok(length);
+    }
+
+    let Some(hole) = first_hole(array, length)? else {
+      return Ok(length);
+    };
+    if self.holes == Holes::Reject {
+      return Err(with_path(
+        invalid_arg("Array has a hole"),
+        &hole.to_string(),
+      ));
+    }
+
+    let mut compacted = hole;
+    for i in hole + 1..length {
+      if array.has_element(i)? {
+        let el = array.get_element_unchecked::<JsUnknown>(i)?;
+        array.set_element(compacted, el)?;
+        compacted += 1;
+      }
+    }
+    array.set_named_property("length", env.create_uint32(compacted)?)?;
+    Ok(compacted)
+  }
+
+  /// Like `fill_holes` without mutating the array, compacting creates a copy.
+  /// Returns `None` when holes are rejected and the array has a hole.
+  pub fn check_holes(&self, env: Env, array: JsObject) -> Result<Option<(JsObject, u32)>> {
+    let length = array.get_array_length_unchecked()?;
+    if self.holes == Holes::Undefined {
+      return Ok(Some((array, length)));
+    }
+
+    if first_hole(&array, length)?.is_none() {
+      return Ok(Some((array, length)));
+    }
+    if self.holes == Holes::Reject {
+      return Ok(None);
+    }
+
+    let mut compacted = env.create_empty_array()?;
+    let mut compacted_len = 0;
+    for i in 0..length {
+      if array.has_element(i)? {
+        compacted.set_element(compacted_len, array.get_element_unchecked::<JsUnknown>(i)?)?;
+        compacted_len += 1;
+      }
+    }
+    Ok(Some((compacted, compacted_len)))
+  }
+}
+
+fn first_hole(array: &JsObject, length: u32) -> Result<Option<u32>> {
+  for i in 0..length {
+    if !array.has_element(i)? {
+      return Ok(Some(i));
+    }
+  }
+  Ok(None)
+}
+
+/// Whether a value is an `arguments` object.
+fn is_arguments(env: Env, value: &JsUnknown) -> Result<bool> {
+  let object: JsObject = env.get_global()?.get_named_property_unchecked("Object")?;
+  let prototype: JsObject = object.get_named_property_unchecked("prototype")?;
+  let to_string = prototype.get_named_property_unchecked::<JsFunction>("toString")?;
+  let tag = to_string.call_without_args(Some(&unsafe { value.cast::<JsObject>() }))?;
+  Ok(tag.coerce_to_string()?.into_utf8()?.as_str()? == "[object Arguments]")
+}
+
+/// Freezing of parsed container values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Freeze {
+  Shallow,
+  /// Freeze nested objects and arrays as well.
+  Deep,
+}
+
+impl Freeze {
+  /// Freeze the parsed value of a container once async writes into it, deferred after `pending`, resolved.
+  pub fn apply(
+    self,
+    ctx: &mut ParseContext,
+    pending: usize,
+    value: JsUnknown,
+  ) -> Result<JsUnknown> {
+    if ctx.is_async() && value.is_promise()? {
+      return then(ctx.env, value, move |env, value| self.freeze(env, value))
+        .map(|promise| promise.into_unknown());
+    }
+
+    if ctx.pending_len() > pending {
+      let persistent = PersistentValue::new(ctx.env, &value)?;
+      ctx.defer_after(pending, move |env| self.freeze(env, persistent.get(env)?))?;
+      return Ok(value);
+    }
+
+    self.freeze(ctx.env, value)
+  }
+
+  fn freeze(self, env: Env, value: JsUnknown) -> Result<JsUnknown> {
+    if value.get_type()? == ValueType::Object {
+      let object: JsObject = env.get_global()?.get_named_property_unchecked("Object")?;
+      freeze_object(
+        &object,
+        unsafe { &value.cast::<JsObject>() },
+        self == Freeze::Deep,
+      )?;
+    }
+    Ok(value)
+  }
+}
+
+/// Call `Object.freeze`, nested values are frozen after their parent so cycles end at frozen objects.
+fn freeze_object(object: &JsObject, value: &JsObject, deep: bool) -> Result<()> {
+  // typed arrays with elements can not be frozen
+  if value.is_typedarray()? {
+    return Ok(());
+  }
+
+  let is_frozen = object.get_named_property_unchecked::<JsFunction>("isFrozen")?;
+  if is_frozen
+    .call(None, &[value])?
+    .coerce_to_bool()?
+    .get_value()?
+  {
+    return Ok(());
   }
+  let freeze = object.get_named_property_unchecked::<JsFunction>("freeze")?;
+  freeze.call(None, &[value])?;
+
+  if deep {
+    for key in JsObject::keys(value)? {
+      let property = value.get_named_property_unchecked::<JsUnknown>(&key)?;
+      if property.get_type()? == ValueType::Object {
+        freeze_object(object, unsafe { &property.cast::<JsObject>() }, deep)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+pub fn parse_btype(b_type: &BType, value: JsUnknown, ctx: &mut ParseContext) -> Result<JsUnknown> {
+  get_btype_base(b_type).warn_deprecated(ctx, &value)?;
+  let parsed = match b_type {
+    BType::Undefined(s) => s.parse_with(ctx, value),
+    BType::Null(s) => s.parse_with(ctx, value),
+    BType::Boolean(s) => s.parse_with(ctx, value),
+    BType::Number(s) => s.parse_with(ctx, value),
+    BType::String(s) => s.parse_with(ctx, value),
+    BType::Enum(s) => s.parse_with(ctx, value),
+    BType::Array(s) => s.parse_with(ctx, value),
+    BType::Object(s) => s.parse_with(ctx, value),
+    BType::Instance(s) => s.parse_with(ctx, value),
+    BType::Union(s) => s.parse_with(ctx, value),
+    BType::Tuple(s) => s.parse_with(ctx, value),
+    BType::Conditional(s) => s.parse_with(ctx, value),
+  };
+  parsed.map_err(|err| get_btype_base(b_type).brand_error(err))
 }
 
 // pub fn parse_btype_rc(b_type: &BType, value: Rc<JsUnknown>, env: Env) -> Result<JsUnknown> {
@@ -52,6 +374,24 @@ pub fn parse_btype(b_type: &BType, value: JsUnknown, env: Env) -> Result<JsUnkno
 //   }
 // }
 
+/// Check a value without building errors, returns the output value when valid.
+pub fn check_btype(b_type: &BType, value: JsUnknown, env: Env) -> Result<Option<JsUnknown>> {
+  match b_type {
+    BType::Undefined(s) => s.check_with(env, value),
+    BType::Null(s) => s.check_with(env, value),
+    BType::Boolean(s) => s.check_with(env, value),
+    BType::Number(s) => s.check_with(env, value),
+    BType::String(s) => s.check_with(env, value),
+    BType::Enum(s) => s.check_with(env, value),
+    BType::Array(s) => s.check_with(env, value),
+    BType::Object(s) => s.check_with(env, value),
+    BType::Instance(s) => s.check_with(env, value),
+    BType::Union(s) => s.check_with(env, value),
+    BType::Tuple(s) => s.check_with(env, value),
+    BType::Conditional(s) => s.check_with(env, value),
+  }
+}
+
 pub fn get_btype_base(b_type: &BType) -> &BValueBase {
   match b_type {
     BType::Undefined(s) => s.get_base(),
@@ -59,13 +399,63 @@ pub fn get_btype_base(b_type: &BType) -> &BValueBase {
     BType::Boolean(s) => s.get_base(),
     BType::Number(s) => s.get_base(),
     BType::String(s) => s.get_base(),
+    BType::Enum(s) => s.get_base(),
     BType::Array(s) => s.get_base(),
     BType::Object(s) => s.get_base(),
+    BType::Instance(s) => s.get_base(),
     BType::Union(s) => s.get_base(),
     BType::Tuple(s) => s.get_base(),
+    BType::Conditional(s) => s.get_base(),
+  }
+}
+
+/// Whether parsing a value with this type can return a different value,
+/// in which case containers have to write the result back.
+pub fn transforms_value(b_type: &BType) -> bool {
+  let base = get_btype_base(b_type);
+  let transforms =
+    !base.preprocess.is_empty() || base.effects.iter().any(|effect| effect.transforms());
+
+  match b_type {
+    BType::Union(s) => transforms || s.inner_transforms(),
+    BType::Conditional(s) => transforms || s.inner_transforms(),
+    BType::Array(s) => transforms || s.converts(),
+    BType::Tuple(s) => transforms || s.converts(),
+    _ => transforms,
+  }
+}
+
+/// Get the BType of any schema instance by calling its `_toWrapped` method.
+pub fn unwrap_schema(env: Env, schema: JsObject) -> Result<BType> {
+  let to_wrapped = schema.get_named_property::<JsFunction>("_toWrapped")?;
+  let wrapped = to_wrapped.call_without_args(Some(&schema))?;
+  let wrapped = unsafe { ClassInstance::<BWrapped>::from_napi_value(env.raw(), wrapped.raw())? };
+  Ok(wrapped.inner.clone())
+}
+
+pub fn get_btype_base_mut(b_type: &mut BType) -> &mut BValueBase {
+  match b_type {
+    BType::Undefined(s) => s.get_base_mut(),
+    BType::Null(s) => s.get_base_mut(),
+    BType::Boolean(s) => s.get_base_mut(),
+    BType::Number(s) => s.get_base_mut(),
+    BType::String(s) => s.get_base_mut(),
+    BType::Enum(s) => s.get_base_mut(),
+    BType::Array(s) => s.get_base_mut(),
+    BType::Object(s) => s.get_base_mut(),
+    BType::Instance(s) => s.get_base_mut(),
+    BType::Union(s) => s.get_base_mut(),
+    BType::Tuple(s) => s.get_base_mut(),
+    BType::Conditional(s) => s.get_base_mut(),
   }
 }
 
 pub fn invalid_arg(reason: &str) -> Error {
   Error::new(Status::InvalidArg, reason)
 }
+
+/// Prepend a segment to the path of an error, e.g. `items` for `Invalid value (at items.3)`.
+pub fn with_path(mut error: Error, segment: &str) -> Error {
+  error.path.insert(0, segment.to_string());
+  error
+}
@@ -0,0 +1,131 @@
+use napi::{bindgen_prelude::ClassInstance, Env, JsObject, JsUnknown};
+
+use crate::{
+  common::{check_btype, parse_btype, transforms_value, ClonableJsUnknown},
+  definition::{Definition, DefinitionReader},
+  error::Result,
+  impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter,
+  BType, BValueBase, BWrapped,
+};
+
+/// Parses with `then` when the value is valid for `condition`, like JSON Schema's `if`/`then`/`else`.
+#[napi]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BConditional {
+  base: BValueBase,
+  condition: Box<BType>,
+  then: Box<BType>,
+  /// Values not matching the condition are passed unchanged when there is no else type.
+  otherwise: Option<Box<BType>>,
+}
+
+impl_base_methods!(
+  BConditional,
+  Conditional,
+  "BConditional<R | undefined>",
+  "BConditional<R | null>",
+  "BConditional<R | null | undefined>",
+  "BConditional<Exclude<R, undefined>>",
+  "BConditional<Exclude<R, null>>",
+  "BConditional<T | Extract<R, null | undefined>>",
+  "BConditional<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
+);
+impl_parse_methods!(BConditional);
+
+#[napi]
+impl BConditional {
+  /// For internal use only!
+  #[napi(js_name = "_fromWrapped", ts_return_type = "unknown")]
+  pub fn from_wrapped(
+    condition: ClassInstance<BWrapped>,
+    then: ClassInstance<BWrapped>,
+    otherwise: Option<ClassInstance<BWrapped>>,
+  ) -> BConditional {
+    BConditional {
+      base: BValueBase::default(),
+      condition: Box::new(condition.inner.clone()),
+      then: Box::new(then.inner.clone()),
+      otherwise: otherwise.map(|otherwise| Box::new(otherwise.inner.clone())),
+    }
+  }
+
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "conditional", &self.base)?;
+    definition.set_schema("if", Some(&self.condition))?;
+    definition.set_schema("then", Some(&self.then))?;
+    definition.set_schema("else", self.otherwise.as_deref())?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    Ok(BConditional {
+      base,
+      condition: Box::new(reader.required_schema("if")?),
+      then: Box::new(reader.required_schema("then")?),
+      otherwise: reader.schema("else")?.map(Box::new),
+    })
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let mut obj = writer.env().create_object()?;
+    obj.set_named_property("if", writer.schema(&self.condition)?)?;
+    obj.set_named_property("then", writer.schema(&self.then)?)?;
+    if let Some(otherwise) = &self.otherwise {
+      obj.set_named_property("else", writer.schema(otherwise)?)?;
+    }
+    Ok(obj)
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
+    if self.base.skip_parse(&value)? {
+      return Ok(value);
+    }
+
+    let parsed = match self.branch(ctx.env, value)? {
+      (Some(b_type), value) => parse_btype(b_type, value, ctx)?,
+      (None, value) => value,
+    };
+    self.base.apply_effects(ctx, parsed)
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    let checked = match self.branch(env, value)? {
+      (Some(b_type), value) => check_btype(b_type, value, env)?,
+      (None, value) => Some(value),
+    };
+    match checked {
+      Some(value) => self.base.check_effects(env, value),
+      None => Ok(None),
+    }
+  }
+
+  /// Select the type to use for a value by checking the condition, without mutating the value.
+  fn branch(&self, env: Env, value: JsUnknown) -> Result<(Option<&BType>, JsUnknown)> {
+    let clonable = ClonableJsUnknown::new(env, value)?;
+    let b_type = match check_btype(&self.condition, clonable.create_clone()?, env)? {
+      Some(_) => Some(self.then.as_ref()),
+      None => self.otherwise.as_deref(),
+    };
+    Ok((b_type, clonable.create_clone()?))
+  }
+
+  pub fn inner_transforms(&self) -> bool {
+    transforms_value(&self.then) || self.otherwise.as_deref().is_some_and(transforms_value)
+  }
+
+  /// Create a copy with the then and else types mapped by `f`.
+  pub fn map_inner(&self, f: impl Fn(&BType) -> BType) -> BConditional {
+    BConditional {
+      then: Box::new(f(&self.then)),
+      otherwise: self.otherwise.as_deref().map(|b_type| Box::new(f(b_type))),
+      ..self.clone()
+    }
+  }
+}
@@ -0,0 +1,519 @@
+use std::{cell::RefCell, rc::Rc};
+
+use napi::{
+  sys, CallContext, Env, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue, Status, ValueType,
+};
+
+use crate::{
+  common::{with_path, PersistentValue},
+  error::{format_path, Error, Result},
+};
+
+/// State of a single parse call, passed down to every nested value.
+pub struct ParseContext {
+  pub env: Env,
+  asynchronous: bool,
+  /// Write parsed values into copies of containers instead of the input, set while parsing union members.
+  copies: bool,
+  /// Promises which have to resolve before an async parse resolves.
+  pending: Vec<JsObject>,
+  /// Path of the value being parsed, relative to the value of the context.
+  path: Vec<String>,
+  /// Path of the value of the context, for contexts forked while parsing.
+  base_path: Vec<String>,
+  /// Shared with contexts forked after async steps, so their warnings are part of the result.
+  warnings: Warnings,
+}
+
+/// Warnings collected while parsing, with their full path.
+pub type Warnings = Rc<RefCell<Vec<String>>>;
+
+impl ParseContext {
+  pub fn new(env: Env) -> Self {
+    ParseContext {
+      env,
+      asynchronous: false,
+      copies: false,
+      pending: vec![],
+      path: vec![],
+      base_path: vec![],
+      warnings: Rc::default(),
+    }
+  }
+
+  pub fn new_async(env: Env) -> Self {
+    ParseContext {
+      asynchronous: true,
+      ..ParseContext::new(env)
+    }
+  }
+
+  pub fn is_async(&self) -> bool {
+    self.asynchronous
+  }
+
+  /// Whether a value returned by a JS callback is a promise, which is only allowed when parsing async.
+  pub fn is_deferred(&self, value: &JsUnknown) -> Result<bool> {
+    if !value.is_promise()? {
+      return Ok(false);
+    }
+
+    match self.asynchronous {
+      true => Ok(true),
+      false => Err(async_step_error()),
+    }
+  }
+
+  /// Whether parsed values have to be written back, even when the type does not transform them.
+  pub fn copies(&self) -> bool {
+    self.copies
+  }
+
+  /// The container to parse into, a shallow copy keeping prototype and holes when copying.
+  pub fn writable(&self, container: JsObject) -> Result<JsObject> {
+    if !self.copies {
+      return Ok(container);
+    }
+
+    let global = self.env.get_global()?;
+    if container.is_array()? {
+      let array: JsObject = global.get_named_property_unchecked("Array")?;
+      let prototype: JsObject = array.get_named_property_unchecked("prototype")?;
+      let slice = prototype.get_named_property_unchecked::<JsFunction>("slice")?;
+      return Ok(
+        slice
+          .call_without_args(Some(&container))?
+          .coerce_to_object()?,
+      );
+    }
+
+    let object: JsObject = global.get_named_property_unchecked("Object")?;
+    let prototype = object
+      .get_named_property_unchecked::<JsFunction>("getPrototypeOf")?
+      .call(None, &[&container])?;
+    let copy = object
+      .get_named_property_unchecked::<JsFunction>("create")?
+      .call(None, &[prototype])?;
+    Ok(
+      object
+        .get_named_property_unchecked::<JsFunction>("assign")?
+        .call(None, &[copy, container.into_unknown()])?
+        .coerce_to_object()?,
+    )
+  }
+
+  /// Context to parse a value separately, e.g. once an async step resolved.
+  /// Deferred errors get paths relative to that value, warnings keep their full path.
+  pub fn fork(&self) -> ParseContext {
+    ParseContext {
+      env: self.env,
+      asynchronous: self.asynchronous,
+      copies: self.copies,
+      pending: vec![],
+      path: vec![],
+      base_path: [self.base_path.as_slice(), self.path.as_slice()].concat(),
+      warnings: self.warnings.clone(),
+    }
+  }
+
+  /// Context to parse a union member, which writes into copies and collects its own warnings,
+  /// so a member which does not match leaves neither the input nor the warnings changed.
+  pub fn fork_member(&self) -> ParseContext {
+    ParseContext {
+      copies: true,
+      warnings: Rc::default(),
+      ..self.fork()
+    }
+  }
+
+  /// Take over the warnings and deferred promises of a member which matched.
+  pub fn merge(&mut self, member: ParseContext) {
+    self
+      .warnings
+      .borrow_mut()
+      .append(&mut member.warnings.borrow_mut());
+    self.pending.extend(member.pending);
+  }
+
+  /// Drop a member which did not match, its deferred promises are no longer awaited.
+  pub fn discard(self) -> Result<()> {
+    for promise in self.pending {
+      ignore_rejection(self.env, promise)?;
+    }
+    Ok(())
+  }
+
+  /// Await a promise before the parse resolves, a rejection fails the parse.
+  pub fn defer(&mut self, promise: JsObject) {
+    self.pending.push(promise);
+  }
+
+  /// Parse a nested value at `segment`, which is added to the path of its warnings and deferred errors.
+  pub fn nested<T>(&mut self, segment: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+    self.path.push(segment.to_string());
+    let result = f(self);
+    self.path.pop();
+    result
+  }
+
+  /// Write the resolved value of a promise into `target` at `segment`, e.g. the parsed value of an object property.
+  pub fn defer_write<F>(
+    &mut self,
+    target: &JsObject,
+    segment: &str,
+    promise: JsUnknown,
+    write: F,
+  ) -> Result<()>
+  where
+    F: 'static + Fn(&mut JsObject, JsUnknown) -> Result<()>,
+  {
+    let target = PersistentValue::new(self.env, target)?;
+    let written = then(self.env, promise, move |env, value| {
+      write(&mut target.get(env)?, value)?;
+      Ok(env.get_undefined()?.into_unknown())
+    })?;
+    let path = [self.path.as_slice(), &[segment.to_string()]].concat();
+    self.defer(with_rejection_path(self.env, written, path)?);
+    Ok(())
+  }
+
+  /// Run `f` once the promises deferred after `len` resolved, before the parse resolves.
+  /// A promise returned by `f` is awaited as well.
+  pub fn defer_after<F>(&mut self, len: usize, f: F) -> Result<()>
+  where
+    F: 'static + Fn(Env) -> Result<JsUnknown>,
+  {
+    let env = self.env;
+    let mut pending = env.create_array_with_length(self.pending.len() - len)?;
+    for (i, promise) in self.pending[len..].iter().enumerate() {
+      let promise = unsafe { JsObject::from_raw_unchecked(env.raw(), promise.raw()) };
+      pending.set_element(i as u32, promise)?;
+    }
+    let all = call_promise_static(env, "all", pending)?;
+
+    // only errors of `f` get the path, the awaited promises already added theirs
+    let path = self.path.clone();
+    let done = then(env, all.into_unknown(), move |env, _| {
+      let result = f(env).map_err(|err| {
+        path
+          .iter()
+          .rev()
+          .fold(err, |err, segment| with_path(err, segment))
+      })?;
+      match result.is_promise()? {
+        true => {
+          Ok(with_rejection_path(env, result.coerce_to_object()?, path.clone())?.into_unknown())
+        }
+        false => Ok(result),
+      }
+    })?;
+    self.defer(done);
+    Ok(())
+  }
+
+  pub fn pending_len(&self) -> usize {
+    self.pending.len()
+  }
+
+  /// Add a warning for the value being parsed, with its path like errors.
+  pub fn warn(&mut self, warning: String) {
+    let path = [self.base_path.as_slice(), self.path.as_slice()].concat();
+    self
+      .warnings
+      .borrow_mut()
+      .push(format_path(&warning, &path));
+  }
+
+  pub fn take_warnings(&mut self) -> Option<Vec<String>> {
+    take_warnings(&self.warnings)
+  }
+
+  /// Warnings of the parse, which async steps add to until the parse resolved.
+  pub fn warnings(&self) -> Warnings {
+    self.warnings.clone()
+  }
+
+  /// Create a promise resolving with the parsed value once all deferred promises resolved.
+  pub fn settle(self, result: Result<JsUnknown>) -> Result<JsObject> {
+    let env = self.env;
+    let value = match result {
+      Ok(value) => value,
+      Err(error) => {
+        let rejected = promise_reject(env, error);
+        for promise in self.pending {
+          ignore_rejection(env, promise)?;
+        }
+        return rejected;
+      }
+    };
+
+    if self.pending.is_empty() {
+      return call_promise_static(env, "resolve", value);
+    }
+
+    let mut pending = env.create_array_with_length(self.pending.len())?;
+    for (i, promise) in self.pending.into_iter().enumerate() {
+      pending.set_element(i as u32, promise)?;
+    }
+    let all = call_promise_static(env, "all", pending)?;
+
+    let value = PersistentValue::new(env, value)?;
+    then(env, all.into_unknown(), move |env, _| value.get(env))
+  }
+
+  /// Like `settle()` for the value of a parse call, errors it rejects with get their path
+  /// added to the message, like errors thrown when parsing synchronously.
+  pub fn settle_root(self, result: Result<JsUnknown>) -> Result<JsObject> {
+    let env = self.env;
+    let settled = self.settle(result)?;
+    then_catch(
+      env,
+      settled,
+      |_, value| Ok(value),
+      |env, reason| {
+        if is_invalid(&reason)? {
+          let mut error = unsafe { reason.cast::<JsObject>() };
+          let path = rejection_path(&error)?;
+          if !path.is_empty() {
+            let message = error
+              .get_named_property::<JsUnknown>("message")?
+              .coerce_to_string()?
+              .into_utf8()?
+              .into_owned()?;
+            error
+              .set_named_property("message", env.create_string(&format_path(&message, &path))?)?;
+            error.delete_named_property("path")?;
+          }
+        }
+        rethrow(env, reason)
+      },
+    )
+  }
+
+  /// Resolve with the value of a member once its deferred promises resolved, its warnings are
+  /// added to `parent` then. Rejects when an async step of the member failed.
+  pub fn settle_member(self, parent: &ParseContext, value: JsUnknown) -> Result<JsObject> {
+    let env = self.env;
+    let parent_warnings = parent.warnings.clone();
+    let warnings = self.warnings.clone();
+    let settled = self.settle(Ok(value))?;
+    then(env, settled.into_unknown(), move |_, value| {
+      parent_warnings
+        .borrow_mut()
+        .append(&mut warnings.borrow_mut());
+      Ok(value)
+    })
+  }
+}
+
+/// Fail on promises returned by JS callbacks when checking or parsing synchronously.
+pub fn ensure_sync(value: &JsUnknown) -> Result<()> {
+  match value.is_promise()? {
+    true => Err(async_step_error()),
+    false => Ok(()),
+  }
+}
+
+fn async_step_error() -> Error {
+  Error::new(
+    Status::GenericFailure,
+    "Encountered an async refinement or transform, use parseAsync() instead",
+  )
+}
+
+fn take_warnings(warnings: &Warnings) -> Option<Vec<String>> {
+  let mut warnings = warnings.borrow_mut();
+  match warnings.is_empty() {
+    true => None,
+    false => Some(std::mem::take(&mut *warnings)),
+  }
+}
+
+/// Call `promise.then()` with a rust closure.
+pub fn then<F>(env: Env, promise: JsUnknown, on_fulfilled: F) -> Result<JsObject>
+where
+  F: 'static + Fn(Env, JsUnknown) -> Result<JsUnknown>,
+{
+  let promise: JsObject = promise.coerce_to_object()?;
+  let on_fulfilled = env.create_function_from_closure("onFulfilled", move |ctx| {
+    reject_on_error(*ctx.env, on_fulfilled(*ctx.env, ctx.get::<JsUnknown>(0)?))
+  })?;
+
+  Ok(
+    promise
+      .get_named_property::<JsFunction>("then")?
+      .call(Some(&promise), &[on_fulfilled])?
+      .coerce_to_object()?,
+  )
+}
+
+/// Call `promise.then()` with rust closures for both outcomes.
+pub fn then_catch<F, R>(
+  env: Env,
+  promise: JsObject,
+  on_fulfilled: F,
+  on_rejected: R,
+) -> Result<JsObject>
+where
+  F: 'static + Fn(Env, JsUnknown) -> Result<JsUnknown>,
+  R: 'static + Fn(Env, JsUnknown) -> Result<JsUnknown>,
+{
+  let on_fulfilled = env.create_function_from_closure("onFulfilled", move |ctx| {
+    reject_on_error(*ctx.env, on_fulfilled(*ctx.env, ctx.get::<JsUnknown>(0)?))
+  })?;
+  let on_rejected = env.create_function_from_closure("onRejected", move |ctx| {
+    reject_on_error(*ctx.env, on_rejected(*ctx.env, ctx.get::<JsUnknown>(0)?))
+  })?;
+
+  Ok(
+    promise
+      .get_named_property::<JsFunction>("then")?
+      .call(
+        Some(&promise),
+        &[on_fulfilled.into_unknown(), on_rejected.into_unknown()],
+      )?
+      .coerce_to_object()?,
+  )
+}
+
+/// Result of a promise callback, errors reject with their path kept apart like `promise_reject()`.
+fn reject_on_error(env: Env, result: Result<JsUnknown>) -> napi::Result<JsUnknown> {
+  match result {
+    Ok(value) => Ok(value),
+    Err(error) => Ok(promise_reject(env, error)?.into_unknown()),
+  }
+}
+
+/// Whether a rejection reason is an error of a value which did not pass, not one thrown by a callback.
+pub fn is_invalid(reason: &JsUnknown) -> Result<bool> {
+  if reason.get_type()? != ValueType::Object {
+    return Ok(false);
+  }
+  let reason = unsafe { reason.cast::<JsObject>() };
+  let code = reason.get_named_property::<JsUnknown>("code")?;
+  match code.get_type()? {
+    ValueType::String => Ok(code.coerce_to_string()?.into_utf8()?.as_str()? == "InvalidArg"),
+    _ => Ok(false),
+  }
+}
+
+/// Reject with `reason` from within a promise callback.
+pub fn rethrow(env: Env, reason: JsUnknown) -> Result<JsUnknown> {
+  Ok(call_promise_static(env, "reject", reason)?.into_unknown())
+}
+
+/// Prepend `path` to the path of errors a deferred promise rejects with, like `with_path`.
+fn with_rejection_path(env: Env, promise: JsObject, path: Vec<String>) -> Result<JsObject> {
+  if path.is_empty() {
+    return Ok(promise);
+  }
+
+  let on_fulfilled = |env: Env, _| Ok(env.get_undefined()?.into_unknown());
+  then_catch(env, promise, on_fulfilled, move |env, reason| {
+    if is_invalid(&reason)? {
+      let mut error = unsafe { reason.cast::<JsObject>() };
+      let path = [path.clone(), rejection_path(&error)?].concat();
+      error.set_named_property("path", path)?;
+    }
+    rethrow(env, reason)
+  })
+}
+
+/// Path of an error a deferred promise rejected with, set by `promise_reject()`.
+fn rejection_path(error: &JsObject) -> Result<Vec<String>> {
+  match error.get_named_property::<JsUnknown>("path")?.is_array()? {
+    true => Ok(error.get_named_property::<Vec<String>>("path")?),
+    false => Ok(vec![]),
+  }
+}
+
+/// Turn the promise returned by `parseAsync()` into a promise resolving with a `ParseSafe` result,
+/// with the warnings collected until then.
+pub fn into_parse_safe(env: Env, promise: JsObject, warnings: Warnings) -> Result<JsObject> {
+  let fulfilled_warnings = warnings.clone();
+  let on_fulfilled = env.create_function_from_closure("onFulfilled", move |ctx: CallContext| {
+    let mut res = ctx.env.create_object()?;
+    res.set_named_property("success", ctx.env.get_boolean(true)?)?;
+    res.set_named_property("data", ctx.get::<JsUnknown>(0)?)?;
+    if let Some(warnings) = take_warnings(&fulfilled_warnings) {
+      res.set_named_property("warnings", warnings)?;
+    }
+    Ok(res)
+  })?;
+  let on_rejected = env.create_function_from_closure("onRejected", move |ctx: CallContext| {
+    let error = ctx.get::<JsUnknown>(0)?;
+    let reason = match error.get_type()? {
+      ValueType::Object => error.coerce_to_object()?.get_named_property("message")?,
+      _ => error.coerce_to_string()?,
+    };
+
+    let mut res = ctx.env.create_object()?;
+    res.set_named_property("success", ctx.env.get_boolean(false)?)?;
+    res.set_named_property("reason", reason)?;
+    if let Some(warnings) = take_warnings(&warnings) {
+      res.set_named_property("warnings", warnings)?;
+    }
+    Ok(res)
+  })?;
+
+  Ok(
+    promise
+      .get_named_property::<JsFunction>("then")?
+      .call(
+        Some(&promise),
+        &[on_fulfilled.into_unknown(), on_rejected.into_unknown()],
+      )?
+      .coerce_to_object()?,
+  )
+}
+
+/// Prevent unhandled rejections of promises which are no longer awaited.
+fn ignore_rejection(env: Env, promise: JsObject) -> Result<()> {
+  let on_rejected =
+    env.create_function_from_closure("onRejected", |ctx| ctx.env.get_undefined())?;
+  promise
+    .get_named_property::<JsFunction>("catch")?
+    .call(Some(&promise), &[on_rejected])?;
+  Ok(())
+}
+
+/// Create a rejected promise, a pending JS exception (e.g. thrown by a callback) is used as reason.
+/// Other errors get their status as `code`, like errors thrown when parsing synchronously.
+/// Errors of invalid values keep their path as `path` until `settle_root()` adds it to the message.
+fn promise_reject(env: Env, mut error: Error) -> Result<JsObject> {
+  let reason = match error.status {
+    Status::PendingException => take_exception(env)?,
+    status => {
+      let path = match status {
+        Status::InvalidArg => std::mem::take(&mut error.path),
+        _ => vec![],
+      };
+      let mut reason = env.create_error(error.into())?;
+      reason.set_named_property("code", env.create_string(status.as_ref())?)?;
+      if !path.is_empty() {
+        reason.set_named_property("path", path)?;
+      }
+      reason.into_unknown()
+    }
+  };
+  call_promise_static(env, "reject", reason)
+}
+
+fn take_exception(env: Env) -> Result<JsUnknown> {
+  let mut exception = std::ptr::null_mut();
+  let status = unsafe { sys::napi_get_and_clear_last_exception(env.raw(), &mut exception) };
+  if status != sys::Status::napi_ok {
+    return Err(Error::from_status(Status::from(status)));
+  }
+  Ok(unsafe { JsUnknown::from_raw(env.raw(), exception) }?)
+}
+
+fn call_promise_static<V: NapiRaw>(env: Env, method: &str, value: V) -> Result<JsObject> {
+  let promise: JsObject = env.get_global()?.get_named_property_unchecked("Promise")?;
+  Ok(
+    promise
+      .get_named_property::<JsFunction>(method)?
+      .call(Some(&promise), &[value])?
+      .coerce_to_object()?,
+  )
+}
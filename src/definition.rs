@@ -0,0 +1,611 @@
+use std::fmt::Display;
+
+use napi::{
+  bindgen_prelude::ToNapiValue, Env, JsNumber, JsObject, JsString, JsUnknown, NapiRaw, NapiValue,
+  ValueType,
+};
+
+use crate::{
+  array::BArray,
+  boolean::BBoolean,
+  common::{invalid_arg, with_path, ArrayOptions, Freeze, Holes, PersistentValue},
+  conditional::BConditional,
+  effect::Effect,
+  enumeration::BEnum,
+  error::{Error, Result},
+  metadata::Metadata,
+  null::BNull,
+  number::BNumber,
+  object::BObject,
+  string::BString,
+  tuple::BTuple,
+  undefined::BUndefined,
+  union::BUnion,
+  BType, BValueBase,
+};
+
+/// Plain object describing a schema, returned by `toDefinition()`.
+/// Optional constraints are only set when used.
+pub struct Definition {
+  env: Env,
+  obj: JsObject,
+}
+
+impl Definition {
+  pub fn new(env: Env, type_name: &str, base: &BValueBase) -> Result<Self> {
+    let mut definition = Definition {
+      env,
+      obj: env.create_object()?,
+    };
+    definition.set_str("type", Some(type_name))?;
+    definition.set("optional", env.get_boolean(base.optional)?)?;
+    definition.set("nullable", env.get_boolean(base.nullable)?)?;
+    definition.set_str("brand", base.brand.as_deref())?;
+
+    let meta = &base.meta;
+    definition.set_str("title", meta.title.as_deref())?;
+    definition.set_str("description", meta.description.as_deref())?;
+    if let Some(examples) = &meta.examples {
+      definition.set("examples", examples.get::<JsObject>(env)?)?;
+    }
+    definition.set_str("deprecated", meta.deprecated.as_deref())?;
+    if let Some(custom) = &meta.custom {
+      definition.set("meta", custom.get::<JsObject>(env)?)?;
+    }
+
+    definition.set_flag("preprocess", !base.preprocess.is_empty())?;
+    if !base.effects.is_empty() {
+      let mut effects = env.create_array_with_length(base.effects.len())?;
+      for (i, effect) in base.effects.iter().enumerate() {
+        effects.set_element(i as u32, effect_definition(env, effect)?)?;
+      }
+      definition.set("effects", effects)?;
+    }
+
+    Ok(definition)
+  }
+
+  pub fn set<V: NapiRaw>(&mut self, key: &str, value: V) -> Result<()> {
+    Ok(self.obj.set_named_property(key, value)?)
+  }
+
+  pub fn set_str(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+    match value {
+      Some(value) => self.set(key, self.env.create_string(value)?),
+      None => Ok(()),
+    }
+  }
+
+  pub fn set_u32(&mut self, key: &str, value: Option<u32>) -> Result<()> {
+    match value {
+      Some(value) => self.set(key, self.env.create_uint32(value)?),
+      None => Ok(()),
+    }
+  }
+
+  pub fn set_f64(&mut self, key: &str, value: Option<f64>) -> Result<()> {
+    match value {
+      Some(value) => self.set(key, self.env.create_double(value)?),
+      None => Ok(()),
+    }
+  }
+
+  /// Set `true` when enabled, disabled flags are left out.
+  pub fn set_flag(&mut self, key: &str, enabled: bool) -> Result<()> {
+    match enabled {
+      true => self.set(key, self.env.get_boolean(true)?),
+      false => Ok(()),
+    }
+  }
+
+  pub fn set_strings(&mut self, key: &str, values: &[String]) -> Result<()> {
+    let mut array = self.env.create_array_with_length(values.len())?;
+    for (i, value) in values.iter().enumerate() {
+      array.set_element(i as u32, self.env.create_string(value)?)?;
+    }
+    self.set(key, array)
+  }
+
+  pub fn set_schema(&mut self, key: &str, b_type: Option<&BType>) -> Result<()> {
+    match b_type {
+      Some(b_type) => self.set(key, btype_definition(self.env, b_type)?),
+      None => Ok(()),
+    }
+  }
+
+  pub fn set_schemas<'a>(
+    &mut self,
+    key: &str,
+    b_types: impl ExactSizeIterator<Item = &'a BType>,
+  ) -> Result<()> {
+    let mut array = self.env.create_array_with_length(b_types.len())?;
+    for (i, b_type) in b_types.enumerate() {
+      array.set_element(i as u32, btype_definition(self.env, b_type)?)?;
+    }
+    self.set(key, array)
+  }
+
+  /// Set an object of schemas, keys are sorted to make definitions deterministic.
+  pub fn set_schema_map<'a>(
+    &mut self,
+    key: &str,
+    b_types: impl Iterator<Item = (&'a String, &'a BType)>,
+  ) -> Result<()> {
+    let mut b_types: Vec<_> = b_types.collect();
+    b_types.sort_by_key(|(key, _)| *key);
+
+    let mut obj = self.env.create_object()?;
+    for (key, b_type) in b_types {
+      obj.set_named_property(key, btype_definition(self.env, b_type)?)?;
+    }
+    self.set(key, obj)
+  }
+
+  pub fn set_array_options(&mut self, options: &ArrayOptions) -> Result<()> {
+    let holes = match options.holes {
+      Holes::Undefined => None,
+      Holes::Reject => Some("reject"),
+      Holes::Compact => Some("compact"),
+    };
+    self.set_str("holes", holes)?;
+    self.set_flag("arrayLike", options.array_like)
+  }
+
+  pub fn set_readonly(&mut self, readonly: Option<Freeze>) -> Result<()> {
+    let readonly = readonly.map(|freeze| match freeze {
+      Freeze::Shallow => "shallow",
+      Freeze::Deep => "deep",
+    });
+    self.set_str("readonly", readonly)
+  }
+
+  pub fn env(&self) -> Env {
+    self.env
+  }
+
+  pub fn into_object(self) -> JsObject {
+    self.obj
+  }
+}
+
+/// JS functions of effects can not be described, only their kind is included.
+fn effect_definition(env: Env, effect: &Effect) -> Result<JsObject> {
+  let type_name = match effect {
+    Effect::Refine { .. } => "refine",
+    Effect::SuperRefine(_) => "superRefine",
+    Effect::Transform(_) => "transform",
+    Effect::Pipe(_) => "pipe",
+  };
+
+  let mut obj = env.create_object()?;
+  obj.set_named_property("type", env.create_string(type_name)?)?;
+  match effect {
+    Effect::Refine { message, .. } => {
+      obj.set_named_property("message", env.create_string(message)?)?
+    }
+    Effect::Pipe(b_type) => obj.set_named_property("schema", btype_definition(env, b_type)?)?,
+    _ => {}
+  }
+  Ok(obj)
+}
+
+pub fn btype_definition(env: Env, b_type: &BType) -> Result<JsObject> {
+  match b_type {
+    BType::Undefined(s) => s.definition(env),
+    BType::Null(s) => s.definition(env),
+    BType::Boolean(s) => s.definition(env),
+    BType::Number(s) => s.definition(env),
+    BType::String(s) => s.definition(env),
+    BType::Enum(s) => s.definition(env),
+    BType::Array(s) => s.definition(env),
+    BType::Object(s) => s.definition(env),
+    BType::Instance(s) => s.definition(env),
+    BType::Union(s) => s.definition(env),
+    BType::Tuple(s) => s.definition(env),
+    BType::Conditional(s) => s.definition(env),
+  }
+}
+
+pub fn btype_from_definition(env: Env, definition: JsUnknown) -> Result<BType> {
+  let mut reader = DefinitionReader::new(env, definition)?;
+  let type_name = reader.required_str("type")?;
+  let base = reader.base()?;
+
+  let b_type = match type_name.as_str() {
+    "undefined" => BType::Undefined(BUndefined::from_definition(base)),
+    "null" => BType::Null(BNull::from_definition(base)),
+    "boolean" => BType::Boolean(BBoolean::from_definition(&mut reader, base)?),
+    "number" => BType::Number(BNumber::from_definition(&mut reader, base)?),
+    "string" => BType::String(BString::from_definition(&mut reader, base)?),
+    "enum" => BType::Enum(BEnum::from_definition(&mut reader, base)?),
+    "array" => BType::Array(BArray::from_definition(&mut reader, base)?),
+    "object" => BType::Object(BObject::from_definition(&mut reader, base)?),
+    "instance" => {
+      return Err(invalid_arg(
+        "Instance schemas can not be restored from a definition",
+      ))
+    }
+    "union" => BType::Union(BUnion::from_definition(&mut reader, base)?),
+    "tuple" => BType::Tuple(BTuple::from_definition(&mut reader, base)?),
+    "conditional" => BType::Conditional(BConditional::from_definition(&mut reader, base)?),
+    _ => {
+      return Err(with_path(
+        invalid_arg(format!("Unknown type '{}'", type_name).as_str()),
+        "type",
+      ))
+    }
+  };
+
+  reader.finish()?;
+  Ok(b_type)
+}
+
+/// Create a JS instance of the schema class of `b_type`.
+pub fn btype_into_js(env: Env, b_type: BType) -> Result<JsUnknown> {
+  let raw = env.raw();
+  let value = unsafe {
+    match b_type {
+      BType::Undefined(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Null(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Boolean(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Number(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::String(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Enum(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Array(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Object(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Instance(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Union(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Tuple(s) => ToNapiValue::to_napi_value(raw, s),
+      BType::Conditional(s) => ToNapiValue::to_napi_value(raw, s),
+    }
+  }?;
+  Ok(unsafe { JsUnknown::from_raw(raw, value) }?)
+}
+
+/// Fail when `min` is higher than `max`, named by their definition keys.
+pub fn check_range<T: PartialOrd + Display>(
+  min_key: &str,
+  min: Option<T>,
+  max_key: &str,
+  max: Option<T>,
+) -> Result<()> {
+  match (min, max) {
+    (Some(min), Some(max)) if min > max => Err(invalid_arg(
+      format!(
+        "'{}' ({}) is higher than '{}' ({})",
+        min_key, min, max_key, max
+      )
+      .as_str(),
+    )),
+    _ => Ok(()),
+  }
+}
+
+/// Reads the keys of a definition object, errors include the path of the invalid key.
+/// Keys which were never read are reported as unknown by `finish()`.
+pub struct DefinitionReader {
+  env: Env,
+  obj: JsObject,
+  read: Vec<String>,
+}
+
+impl DefinitionReader {
+  pub fn new(env: Env, value: JsUnknown) -> Result<Self> {
+    let value_type = value.get_type()?;
+    if value_type != ValueType::Object || value.is_array()? {
+      let got = match value.is_array()? {
+        true => "Array".to_string(),
+        false => value_type.to_string(),
+      };
+      return Err(invalid_arg(format!("expect Object, got: {}", got).as_str()));
+    }
+
+    Ok(DefinitionReader {
+      env,
+      obj: unsafe { value.cast::<JsObject>() },
+      read: vec![],
+    })
+  }
+
+  pub fn env(&self) -> Env {
+    self.env
+  }
+
+  /// Read a key, undefined values are treated as missing.
+  pub fn get(&mut self, key: &str) -> Result<Option<JsUnknown>> {
+    self.read.push(key.to_string());
+    let value = self.obj.get_named_property::<JsUnknown>(key)?;
+    match value.get_type()? {
+      ValueType::Undefined => Ok(None),
+      _ => Ok(Some(value)),
+    }
+  }
+
+  /// Whether a key is set, without reading it.
+  pub fn has(&self, key: &str) -> Result<bool> {
+    let value = self.obj.get_named_property::<JsUnknown>(key)?;
+    Ok(value.get_type()? != ValueType::Undefined)
+  }
+
+  fn get_typed(&mut self, key: &str, expected: ValueType) -> Result<Option<JsUnknown>> {
+    match self.get(key)? {
+      Some(value) if value.get_type()? != expected => Err(with_path(
+        invalid_arg(format!("expect {}, got: {}", expected, value.get_type()?).as_str()),
+        key,
+      )),
+      value => Ok(value),
+    }
+  }
+
+  pub fn str(&mut self, key: &str) -> Result<Option<String>> {
+    match self.get_typed(key, ValueType::String)? {
+      Some(value) => Ok(Some(js_string(value)?)),
+      None => Ok(None),
+    }
+  }
+
+  pub fn required_str(&mut self, key: &str) -> Result<String> {
+    self.str(key)?.ok_or_else(|| missing(key))
+  }
+
+  /// Read a string which has to be one of `choices`.
+  pub fn choice<'a>(&mut self, key: &str, choices: &[&'a str]) -> Result<Option<&'a str>> {
+    let Some(value) = self.str(key)? else {
+      return Ok(None);
+    };
+    match choices.iter().find(|choice| **choice == value) {
+      Some(choice) => Ok(Some(choice)),
+      None => Err(with_path(
+        invalid_arg(format!("Value '{}' is not one of [{}]", value, choices.join(", ")).as_str()),
+        key,
+      )),
+    }
+  }
+
+  pub fn f64(&mut self, key: &str) -> Result<Option<f64>> {
+    match self.get_typed(key, ValueType::Number)? {
+      Some(value) => Ok(Some(unsafe { value.cast::<JsNumber>() }.get_double()?)),
+      None => Ok(None),
+    }
+  }
+
+  pub fn u32(&mut self, key: &str) -> Result<Option<u32>> {
+    match self.f64(key)? {
+      Some(n) if n.fract() != 0. || !(0. ..=u32::MAX as f64).contains(&n) => Err(with_path(
+        invalid_arg(format!("expect a non-negative integer, got: {}", n).as_str()),
+        key,
+      )),
+      n => Ok(n.map(|n| n as u32)),
+    }
+  }
+
+  pub fn bool(&mut self, key: &str) -> Result<Option<bool>> {
+    match self.get_typed(key, ValueType::Boolean)? {
+      Some(value) => Ok(Some(value.coerce_to_bool()?.get_value()?)),
+      None => Ok(None),
+    }
+  }
+
+  /// Read a boolean flag, missing flags are disabled.
+  pub fn flag(&mut self, key: &str) -> Result<bool> {
+    Ok(self.bool(key)?.unwrap_or(false))
+  }
+
+  /// Read a plain object, arrays are rejected.
+  pub fn object(&mut self, key: &str) -> Result<Option<JsObject>> {
+    match self.get_typed(key, ValueType::Object)? {
+      Some(value) if value.is_array()? => {
+        Err(with_path(invalid_arg("expect Object, got: Array"), key))
+      }
+      value => Ok(value.map(|value| unsafe { value.cast::<JsObject>() })),
+    }
+  }
+
+  /// Read an array object, plain objects are rejected.
+  pub fn array_object(&mut self, key: &str) -> Result<Option<JsObject>> {
+    match self.get_typed(key, ValueType::Object)? {
+      Some(value) if !value.is_array()? => {
+        Err(with_path(invalid_arg("expect Array, got: Object"), key))
+      }
+      value => Ok(value.map(|value| unsafe { value.cast::<JsObject>() })),
+    }
+  }
+
+  pub fn array(&mut self, key: &str) -> Result<Option<Vec<JsUnknown>>> {
+    let Some(array) = self.array_object(key)? else {
+      return Ok(None);
+    };
+    let length = array.get_array_length()?;
+    let elements = (0..length)
+      .map(|i| array.get_element::<JsUnknown>(i))
+      .collect::<napi::Result<_>>()?;
+    Ok(Some(elements))
+  }
+
+  pub fn strings(&mut self, key: &str) -> Result<Option<Vec<String>>> {
+    let Some(values) = self.array(key)? else {
+      return Ok(None);
+    };
+    values
+      .into_iter()
+      .enumerate()
+      .map(|(i, value)| match value.get_type()? {
+        ValueType::String => js_string(value),
+        value_type => Err(with_path(
+          invalid_arg(format!("expect String, got: {}", value_type).as_str()),
+          &format!("{}.{}", key, i),
+        )),
+      })
+      .collect::<Result<_>>()
+      .map(Some)
+  }
+
+  /// Read an object of strings, entries keep the key order of the object.
+  pub fn string_map(&mut self, key: &str) -> Result<Option<Vec<(String, String)>>> {
+    let Some(obj) = self.object(key)? else {
+      return Ok(None);
+    };
+    let mut reader = DefinitionReader::new(self.env, obj.into_unknown())?;
+    let keys = reader.keys()?;
+    keys
+      .into_iter()
+      .map(|entry| {
+        let value = reader.str(&entry)?.ok_or_else(|| missing(&entry))?;
+        Ok((entry, value))
+      })
+      .collect::<Result<_>>()
+      .map(Some)
+      .map_err(|err| with_path(err, key))
+  }
+
+  pub fn schema(&mut self, key: &str) -> Result<Option<BType>> {
+    match self.get(key)? {
+      Some(value) => btype_from_definition(self.env, value)
+        .map(Some)
+        .map_err(|err| with_path(err, key)),
+      None => Ok(None),
+    }
+  }
+
+  pub fn required_schema(&mut self, key: &str) -> Result<BType> {
+    self.schema(key)?.ok_or_else(|| missing(key))
+  }
+
+  pub fn schemas(&mut self, key: &str) -> Result<Option<Vec<BType>>> {
+    let Some(values) = self.array(key)? else {
+      return Ok(None);
+    };
+    values
+      .into_iter()
+      .enumerate()
+      .map(|(i, value)| {
+        btype_from_definition(self.env, value)
+          .map_err(|err| with_path(with_path(err, &i.to_string()), key))
+      })
+      .collect::<Result<_>>()
+      .map(Some)
+  }
+
+  /// Read an object of schemas, entries keep the key order of the object.
+  pub fn schema_map(&mut self, key: &str) -> Result<Option<Vec<(String, BType)>>> {
+    let Some(obj) = self.object(key)? else {
+      return Ok(None);
+    };
+    let mut reader = DefinitionReader::new(self.env, obj.into_unknown())?;
+    let keys = reader.keys()?;
+    keys
+      .into_iter()
+      .map(|entry| Ok((entry.clone(), reader.required_schema(&entry)?)))
+      .collect::<Result<_>>()
+      .map(Some)
+      .map_err(|err| with_path(err, key))
+  }
+
+  /// Read a nested object with its own reader, unknown keys of it are reported as well.
+  pub fn nested<T>(
+    &mut self,
+    key: &str,
+    f: impl FnOnce(&mut DefinitionReader) -> Result<T>,
+  ) -> Result<Option<T>> {
+    let Some(obj) = self.object(key)? else {
+      return Ok(None);
+    };
+    let mut reader = DefinitionReader::new(self.env, obj.into_unknown())?;
+    f(&mut reader)
+      .and_then(|value| {
+        reader.finish()?;
+        Ok(value)
+      })
+      .map(Some)
+      .map_err(|err| with_path(err, key))
+  }
+
+  pub fn base(&mut self) -> Result<BValueBase> {
+    if self.flag("preprocess")? || self.get("effects")?.is_some() {
+      return Err(invalid_arg(
+        "Preprocess functions and effects can not be restored from a definition",
+      ));
+    }
+
+    Ok(BValueBase {
+      optional: self.flag("optional")?,
+      nullable: self.flag("nullable")?,
+      brand: self.str("brand")?,
+      meta: Metadata {
+        title: self.str("title")?,
+        description: self.str("description")?,
+        examples: self
+          .array_object("examples")?
+          .map(|examples| PersistentValue::new(self.env, examples))
+          .transpose()?,
+        deprecated: self.str("deprecated")?,
+        custom: self
+          .object("meta")?
+          .map(|custom| PersistentValue::new(self.env, custom))
+          .transpose()?,
+      },
+      ..BValueBase::default()
+    })
+  }
+
+  pub fn array_options(&mut self) -> Result<ArrayOptions> {
+    let options = ArrayOptions {
+      array_like: self.flag("arrayLike")?,
+      ..ArrayOptions::default()
+    };
+    match self.choice("holes", &["undefined", "reject", "compact"])? {
+      Some(policy) => options.with_holes(policy),
+      None => Ok(options),
+    }
+  }
+
+  pub fn readonly(&mut self) -> Result<Option<Freeze>> {
+    Ok(
+      self
+        .choice("readonly", &["shallow", "deep"])?
+        .map(|readonly| match readonly {
+          "deep" => Freeze::Deep,
+          _ => Freeze::Shallow,
+        }),
+    )
+  }
+
+  fn keys(&self) -> Result<Vec<String>> {
+    let names = self.obj.get_property_names()?;
+    let length = names.get_array_length()?;
+    (0..length)
+      .map(|i| js_string(names.get_element::<JsUnknown>(i)?))
+      .collect()
+  }
+
+  /// First key of the object which was not read.
+  pub fn unread_key(&self) -> Result<Option<String>> {
+    Ok(
+      self
+        .keys()?
+        .into_iter()
+        .find(|key| !self.read.contains(key)),
+    )
+  }
+
+  /// Fail on keys which were not read.
+  pub fn finish(&self) -> Result<()> {
+    match self.unread_key()? {
+      Some(key) => Err(invalid_arg(format!("Unknown key '{}'", key).as_str())),
+      None => Ok(()),
+    }
+  }
+}
+
+/// Error for a required key missing in a definition.
+pub fn missing(key: &str) -> Error {
+  with_path(invalid_arg("Got undefined for required value"), key)
+}
+
+pub fn js_string(value: JsUnknown) -> Result<String> {
+  Ok(
+    unsafe { value.cast::<JsString>() }
+      .into_utf8()?
+      .into_owned()?,
+  )
+}
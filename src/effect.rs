@@ -0,0 +1,209 @@
+use napi::{
+  bindgen_prelude::{ClassInstance, Either, FromNapiValue},
+  Env, JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue,
+};
+
+use crate::{
+  common::{check_btype, invalid_arg, parse_btype, with_path, ClonableJsUnknown, PersistentValue},
+  context::{ensure_sync, then, ParseContext},
+  error::{Error, Result},
+  BType,
+};
+
+/// JS function stored in a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsCallback {
+  function: PersistentValue,
+}
+
+impl JsCallback {
+  pub fn new(env: Env, function: JsFunction) -> Result<Self> {
+    Ok(JsCallback {
+      function: PersistentValue::new(env, function)?,
+    })
+  }
+
+  pub fn call(&self, env: Env, args: &[JsUnknown]) -> Result<JsUnknown> {
+    let function: JsFunction = self.function.get(env)?;
+    Ok(function.call(None, args)?)
+  }
+}
+
+/// Custom steps run after the native checks of a value passed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+  Refine { check: JsCallback, message: String },
+  SuperRefine(JsCallback),
+  Transform(JsCallback),
+  Pipe(Box<BType>),
+}
+
+impl Effect {
+  /// Whether the effect can return a different value than it received.
+  pub fn transforms(&self) -> bool {
+    matches!(self, Effect::Transform(_) | Effect::Pipe(_))
+  }
+
+  /// Apply the effect, when parsing async a promise can be returned for the resulting value.
+  pub fn apply(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let env = ctx.env;
+    match self {
+      Effect::Refine { check, message } => {
+        let clonable = ClonableJsUnknown::new(env, value)?;
+        let passed = check.call(env, &[clonable.create_clone()?])?;
+
+        if ctx.is_deferred(&passed)? {
+          let value = PersistentValue::new(env, clonable.create_clone()?)?;
+          let message = message.clone();
+          return then(env, passed, move |env, passed| {
+            match passed.coerce_to_bool()?.get_value()? {
+              true => value.get(env),
+              false => Err(invalid_arg(&message)),
+            }
+          })
+          .map(|promise| promise.into_unknown());
+        }
+
+        match passed.coerce_to_bool()?.get_value()? {
+          true => clonable.create_clone(),
+          false => Err(invalid_arg(message)),
+        }
+      }
+      Effect::SuperRefine(refinement) => {
+        let clonable = ClonableJsUnknown::new(env, value)?;
+        let refinement_ctx = RefinementContext { issues: vec![] }.into_instance(env)?;
+        let ctx_value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), refinement_ctx.raw()) };
+        let returned = refinement.call(env, &[clonable.create_clone()?, ctx_value])?;
+
+        if ctx.is_deferred(&returned)? {
+          let value = PersistentValue::new(env, clonable.create_clone()?)?;
+          let refinement_ctx = PersistentValue::new(env, refinement_ctx)?;
+          return then(env, returned, move |env, _| {
+            let refinement_ctx = refinement_ctx.get::<JsObject>(env)?;
+            let refinement_ctx = unsafe {
+              ClassInstance::<RefinementContext>::from_napi_value(env.raw(), refinement_ctx.raw())?
+            };
+            match refinement_ctx.issues.first() {
+              Some(issue) => Err(issue.to_error()),
+              None => value.get(env),
+            }
+          })
+          .map(|promise| promise.into_unknown());
+        }
+
+        match refinement_ctx.issues.first() {
+          Some(issue) => Err(issue.to_error()),
+          None => clonable.create_clone(),
+        }
+      }
+      Effect::Transform(transform) => {
+        let transformed = transform.call(env, &[value])?;
+        ctx.is_deferred(&transformed)?;
+        Ok(transformed)
+      }
+      Effect::Pipe(b_type) => parse_btype(b_type, value, ctx),
+    }
+  }
+
+  /// Like `apply`, but returns `None` instead of building an error when the value is invalid.
+  pub fn check(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    match self {
+      Effect::Refine { check, .. } => {
+        let clonable = ClonableJsUnknown::new(env, value)?;
+        let passed = check.call(env, &[clonable.create_clone()?])?;
+        ensure_sync(&passed)?;
+
+        match passed.coerce_to_bool()?.get_value()? {
+          true => Ok(Some(clonable.create_clone()?)),
+          false => Ok(None),
+        }
+      }
+      Effect::SuperRefine(refinement) => {
+        let clonable = ClonableJsUnknown::new(env, value)?;
+        let refinement_ctx = RefinementContext { issues: vec![] }.into_instance(env)?;
+        let ctx_value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), refinement_ctx.raw()) };
+        ensure_sync(&refinement.call(env, &[clonable.create_clone()?, ctx_value])?)?;
+
+        match refinement_ctx.issues.is_empty() {
+          true => Ok(Some(clonable.create_clone()?)),
+          false => Ok(None),
+        }
+      }
+      Effect::Transform(transform) => {
+        let transformed = transform.call(env, &[value])?;
+        ensure_sync(&transformed)?;
+        Ok(Some(transformed))
+      }
+      Effect::Pipe(b_type) => check_btype(b_type, value, env),
+    }
+  }
+}
+
+/// Check effects in order, stops at the first effect the value is invalid for.
+pub fn check_effects(effects: &[Effect], env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+  effects
+    .iter()
+    .try_fold(Some(value), |value, effect| match value {
+      Some(value) => effect.check(env, value),
+      None => Ok(None),
+    })
+}
+
+/// Run effects in order, once an effect returns a promise the remaining effects run after it resolved.
+pub fn apply_effects(
+  effects: &[Effect],
+  ctx: &mut ParseContext,
+  value: JsUnknown,
+) -> Result<JsUnknown> {
+  let mut value = value;
+  for (i, effect) in effects.iter().enumerate() {
+    value = effect.apply(ctx, value)?;
+
+    let remaining = &effects[i + 1..];
+    if ctx.is_async() && !remaining.is_empty() && value.is_promise()? {
+      let remaining = remaining.to_vec();
+      let fork = ctx.fork();
+      return then(ctx.env, value, move |_, value| {
+        let mut ctx = fork.fork();
+        let result = apply_effects(&remaining, &mut ctx, value);
+        Ok(ctx.settle(result)?.into_unknown())
+      })
+      .map(|promise| promise.into_unknown());
+    }
+  }
+
+  Ok(value)
+}
+
+#[napi(object)]
+#[derive(Clone)]
+pub struct Issue {
+  pub message: String,
+  /// Path relative to the refined value.
+  pub path: Option<Vec<Either<String, u32>>>,
+}
+
+impl Issue {
+  fn to_error(&self) -> Error {
+    let segments = self.path.iter().flatten().rev();
+    segments.fold(invalid_arg(&self.message), |error, segment| match segment {
+      Either::A(key) => with_path(error, key),
+      Either::B(index) => with_path(error, &index.to_string()),
+    })
+  }
+}
+
+/// Passed to `superRefine` callbacks to report issues.
+/// Parsing fails with the first reported issue.
+#[napi]
+pub struct RefinementContext {
+  issues: Vec<Issue>,
+}
+
+#[napi]
+impl RefinementContext {
+  #[napi]
+  pub fn add_issue(&mut self, issue: Issue) {
+    self.issues.push(issue);
+  }
+}
@@ -0,0 +1,116 @@
+use napi::{Env, JsObject, JsString, JsUnknown, ValueType};
+
+use crate::{
+  common::{invalid_arg, with_path},
+  definition::{Definition, DefinitionReader},
+  error::Result,
+  impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter,
+  BValueBase,
+};
+
+#[napi]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BEnum {
+  base: BValueBase,
+  values: Vec<String>,
+}
+
+impl_base_methods!(
+  BEnum,
+  Enum,
+  "BEnum<R | undefined>",
+  "BEnum<R | null>",
+  "BEnum<R | null | undefined>",
+  "BEnum<Exclude<R, undefined>>",
+  "BEnum<Exclude<R, null>>",
+  "BEnum<T | Extract<R, null | undefined>>",
+  "BEnum<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
+);
+impl_parse_methods!(BEnum);
+
+#[napi]
+impl BEnum {
+  /// For internal use only!
+  #[napi(ts_return_type = "BEnum<unknown>")]
+  pub fn new(values: Vec<String>) -> BEnum {
+    BEnum {
+      base: BValueBase::default(),
+      values,
+    }
+  }
+
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "enum", &self.base)?;
+    definition.set_strings("values", &self.values)?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    let values = reader.strings("values")?.unwrap_or_default();
+    if values.is_empty() {
+      return Err(with_path(invalid_arg("Enum has no values"), "values"));
+    }
+    Ok(BEnum { base, values })
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let env = writer.env();
+    let mut values = env.create_array_with_length(self.values.len())?;
+    for (i, value) in self.values.iter().enumerate() {
+      values.set_element(i as u32, env.create_string(value)?)?;
+    }
+    let mut obj = writer.typed("string")?;
+    obj.set_named_property("enum", values)?;
+    Ok(obj)
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
+    if self.base.skip_parse(&value)? {
+      return Ok(value);
+    }
+
+    let js_string: JsString = value.try_into()?;
+    let js_utf8 = js_string.into_utf8()?;
+    let str = js_utf8.as_str()?;
+
+    if !self.values.iter().any(|v| v == str) {
+      return Err(invalid_arg(
+        format!(
+          "Value '{}' is not one of the enum values [{}]",
+          str,
+          self.values.join(", ")
+        )
+        .as_str(),
+      ));
+    }
+
+    self.base.apply_effects(ctx, js_string.into_unknown())
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    if value.get_type()? != ValueType::String {
+      return Ok(None);
+    }
+    let js_string = unsafe { value.cast::<JsString>() };
+    let js_utf8 = js_string.into_utf8()?;
+    let str = js_utf8.as_str()?;
+    if !self.values.iter().any(|v| v == str) {
+      return Ok(None);
+    }
+
+    self.base.check_effects(env, js_string.into_unknown())
+  }
+
+  /// Allowed values of the enum.
+  #[napi(ts_return_type = "R[]")]
+  pub fn options(&self) -> Vec<String> {
+    self.values.clone()
+  }
+}
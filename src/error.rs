@@ -0,0 +1,62 @@
+use std::fmt;
+
+use napi::{bindgen_prelude::JsError, Status};
+
+/// Error of a parse or a schema conversion. The path of the value which failed is kept apart
+/// from the message, it is only added to the message when the error leaves the crate.
+#[derive(Debug)]
+pub struct Error {
+  pub status: Status,
+  pub reason: String,
+  /// Segments from the outermost value to the value which failed.
+  pub path: Vec<String>,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+  pub fn new(status: Status, reason: impl Into<String>) -> Self {
+    Error {
+      status,
+      reason: reason.into(),
+      path: vec![],
+    }
+  }
+
+  pub fn from_status(status: Status) -> Self {
+    Error::from(napi::Error::from_status(status))
+  }
+}
+
+/// The message with its path, e.g. `Invalid value (at items.3)`.
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&format_path(&self.reason, &self.path))
+  }
+}
+
+impl From<napi::Error> for Error {
+  fn from(error: napi::Error) -> Self {
+    Error::new(error.status, error.reason)
+  }
+}
+
+impl From<Error> for napi::Error {
+  fn from(error: Error) -> Self {
+    napi::Error::new(error.status, error.to_string())
+  }
+}
+
+impl From<Error> for JsError {
+  fn from(error: Error) -> Self {
+    JsError::from(napi::Error::from(error))
+  }
+}
+
+/// Add a path to a message, like errors are shown.
+pub fn format_path(message: &str, path: &[String]) -> String {
+  match path.is_empty() {
+    true => message.to_string(),
+    false => format!("{} (at {})", message, path.join(".")),
+  }
+}
@@ -0,0 +1,132 @@
+use std::{
+  net::{Ipv4Addr, Ipv6Addr},
+  sync::OnceLock,
+};
+
+use regex::Regex;
+
+use crate::{common::invalid_arg, error::Result};
+
+/// Named string formats, checked like the formats of JSON Schema.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StringFormat {
+  DateTime,
+  Date,
+  Time,
+  Email,
+  Hostname,
+  Ipv4,
+  Ipv6,
+  Uri,
+  Uuid,
+}
+
+const FORMATS: [(&str, StringFormat); 9] = [
+  ("date-time", StringFormat::DateTime),
+  ("date", StringFormat::Date),
+  ("time", StringFormat::Time),
+  ("email", StringFormat::Email),
+  ("hostname", StringFormat::Hostname),
+  ("ipv4", StringFormat::Ipv4),
+  ("ipv6", StringFormat::Ipv6),
+  ("uri", StringFormat::Uri),
+  ("uuid", StringFormat::Uuid),
+];
+
+impl StringFormat {
+  pub fn from_name(name: &str) -> Result<Self> {
+    FORMATS
+      .iter()
+      .find(|(format_name, _)| *format_name == name)
+      .map(|(_, format)| *format)
+      .ok_or_else(|| invalid_arg(format!("Unknown string format '{}'", name).as_str()))
+  }
+
+  pub fn name(&self) -> &'static str {
+    FORMATS
+      .iter()
+      .find(|(_, format)| format == self)
+      .map(|(name, _)| *name)
+      .unwrap_or_default()
+  }
+
+  pub fn is_valid(&self, value: &str) -> bool {
+    match self {
+      StringFormat::DateTime => match value.split_once(['T', 't']) {
+        Some((date, time)) => is_date(date) && is_time(time),
+        None => false,
+      },
+      StringFormat::Date => is_date(value),
+      StringFormat::Time => is_time(value),
+      StringFormat::Email => regex(&EMAIL, r"^[^\s@]+@[^\s@]+\.[^\s@]+$").is_match(value),
+      StringFormat::Hostname => is_hostname(value),
+      StringFormat::Ipv4 => value.parse::<Ipv4Addr>().is_ok(),
+      StringFormat::Ipv6 => value.parse::<Ipv6Addr>().is_ok(),
+      StringFormat::Uri => regex(&URI, r"^[A-Za-z][A-Za-z0-9+.-]*:[^\s]*$").is_match(value),
+      StringFormat::Uuid => regex(
+        &UUID,
+        r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+      )
+      .is_match(value),
+    }
+  }
+}
+
+static EMAIL: OnceLock<Regex> = OnceLock::new();
+static URI: OnceLock<Regex> = OnceLock::new();
+static UUID: OnceLock<Regex> = OnceLock::new();
+static DATE: OnceLock<Regex> = OnceLock::new();
+static TIME: OnceLock<Regex> = OnceLock::new();
+
+/// Compile a regex of a format once, the patterns are known to be valid.
+fn regex<'a>(cell: &'a OnceLock<Regex>, pattern: &str) -> &'a Regex {
+  cell.get_or_init(|| Regex::new(pattern).expect("format regex is valid"))
+}
+
+/// Full date of RFC 3339, e.g. `2024-02-29`.
+fn is_date(value: &str) -> bool {
+  let Some(captures) = regex(&DATE, r"^(\d{4})-(\d{2})-(\d{2})$").captures(value) else {
+    return false;
+  };
+  let number = |i: usize| captures[i].parse::<u32>().unwrap_or_default();
+  let (year, month, day) = (number(1), number(2), number(3));
+
+  let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+  let days = match month {
+    2 if leap_year => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    1..=12 => 31,
+    _ => return false,
+  };
+  (1..=days).contains(&day)
+}
+
+/// Full time of RFC 3339 with an offset, e.g. `12:30:00Z` or `12:30:00.5+02:00`.
+fn is_time(value: &str) -> bool {
+  let pattern = r"^(\d{2}):(\d{2}):(\d{2})(\.\d+)?([Zz]|[+-](\d{2}):(\d{2}))$";
+  let Some(captures) = regex(&TIME, pattern).captures(value) else {
+    return false;
+  };
+  let number = |i: usize| {
+    captures
+      .get(i)
+      .map_or(0, |m| m.as_str().parse::<u32>().unwrap_or_default())
+  };
+
+  // seconds of 60 are allowed for leap seconds
+  number(1) <= 23 && number(2) <= 59 && number(3) <= 60 && number(6) <= 23 && number(7) <= 59
+}
+
+fn is_hostname(value: &str) -> bool {
+  let value = value.strip_suffix('.').unwrap_or(value);
+  !value.is_empty()
+    && value.len() <= 253
+    && value.split('.').all(|label| {
+      !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
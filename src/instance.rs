@@ -0,0 +1,113 @@
+use napi::{Env, JsFunction, JsObject, JsString, JsUnknown, ValueType};
+
+use crate::{
+  common::{invalid_arg, PersistentValue},
+  definition::Definition,
+  error::Result,
+  impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter,
+  BValueBase,
+};
+
+#[napi]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BInstance {
+  base: BValueBase,
+  class: PersistentValue,
+  /// Name of the class, used in errors.
+  name: String,
+}
+
+impl_base_methods!(
+  BInstance,
+  Instance,
+  "BInstance<R | undefined>",
+  "BInstance<R | null>",
+  "BInstance<R | null | undefined>",
+  "BInstance<Exclude<R, undefined>>",
+  "BInstance<Exclude<R, null>>",
+  "BInstance<T | Extract<R, null | undefined>>",
+  "BInstance<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
+);
+impl_parse_methods!(BInstance);
+
+#[napi]
+impl BInstance {
+  /// For internal use only!
+  #[napi(ts_return_type = "BInstance<unknown>")]
+  pub fn new(env: Env, class: JsFunction) -> Result<BInstance> {
+    let class = PersistentValue::new(env, class)?;
+    let name = class
+      .get::<JsObject>(env)?
+      .get_named_property::<JsUnknown>("name")?;
+    let name = match name.get_type()? {
+      ValueType::String => unsafe { name.cast::<JsString>() }
+        .into_utf8()?
+        .into_owned()?,
+      _ => String::new(),
+    };
+
+    Ok(BInstance {
+      base: BValueBase::default(),
+      class,
+      name,
+    })
+  }
+
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "instance", &self.base)?;
+    definition.set_str("className", Some(self.class_name()))?;
+    Ok(definition.into_object())
+  }
+
+  pub fn json_schema(&self, _writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    Err(invalid_arg(
+      format!(
+        "Instance of {} can not be represented in JSON Schema",
+        self.class_name()
+      )
+      .as_str(),
+    ))
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
+    if self.base.skip_parse(&value)? {
+      return Ok(value);
+    }
+
+    if !self.is_instance(ctx.env, &value)? {
+      return Err(invalid_arg(
+        format!("Value is not an instance of {}", self.class_name()).as_str(),
+      ));
+    }
+
+    self.base.apply_effects(ctx, value)
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    match self.is_instance(env, &value)? {
+      true => self.base.check_effects(env, value),
+      false => Ok(None),
+    }
+  }
+
+  fn is_instance(&self, env: Env, value: &JsUnknown) -> Result<bool> {
+    if !matches!(value.get_type()?, ValueType::Object | ValueType::Function) {
+      return Ok(false);
+    }
+    Ok(value.instanceof(self.class.get::<JsFunction>(env)?)?)
+  }
+
+  fn class_name(&self) -> &str {
+    match self.name.is_empty() {
+      true => "anonymous class",
+      false => &self.name,
+    }
+  }
+}
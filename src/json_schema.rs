@@ -0,0 +1,1196 @@
+use napi::{Env, JsBoolean, JsNumber, JsObject, JsString, JsUnknown, ValueType};
+
+use crate::{
+  common::{
+    get_btype_base, get_btype_base_mut, invalid_arg, with_path, ClonableJsUnknown, Pattern,
+  },
+  definition::{check_range, js_string, DefinitionReader},
+  error::Result,
+  format::StringFormat,
+  metadata::Metadata,
+  BType, BValueBase,
+};
+
+#[napi(object)]
+pub struct JsonSchemaOptions {
+  /// JSON Schema version of the document, defaults to `'2020-12'`.
+  #[napi(ts_type = "'2020-12' | 'draft-07'")]
+  pub draft: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Draft {
+  Draft07,
+  Draft2020,
+}
+
+impl Draft {
+  fn from_options(options: Option<JsonSchemaOptions>) -> Result<Self> {
+    match options.and_then(|options| options.draft).as_deref() {
+      None | Some("2020-12") => Ok(Draft::Draft2020),
+      Some("draft-07") => Ok(Draft::Draft07),
+      Some(draft) => Err(invalid_arg(
+        format!("Unknown JSON Schema draft '{}'", draft).as_str(),
+      )),
+    }
+  }
+
+  fn uri(&self) -> &'static str {
+    match self {
+      Draft::Draft07 => "http://json-schema.org/draft-07/schema#",
+      Draft::Draft2020 => "https://json-schema.org/draft/2020-12/schema",
+    }
+  }
+
+  fn defs_key(&self) -> &'static str {
+    match self {
+      Draft::Draft07 => "definitions",
+      Draft::Draft2020 => "$defs",
+    }
+  }
+}
+
+/// Create a JSON Schema document for `b_type`, container schemas used more than once are put in the definitions.
+pub fn to_json_schema(
+  env: Env,
+  b_type: &BType,
+  options: Option<JsonSchemaOptions>,
+) -> Result<JsObject> {
+  let draft = Draft::from_options(options)?;
+
+  // the first pass only counts container schemas to find the shared ones
+  let mut counter = JsonSchemaWriter::new(env, draft, None);
+  counter.schema(b_type)?;
+  let shared = counter
+    .seen
+    .into_iter()
+    .filter(|(_, count)| *count > 1)
+    .map(|(b_type, _)| b_type)
+    .collect();
+
+  let mut writer = JsonSchemaWriter::new(env, draft, Some(shared));
+  let root = writer.inline_schema(b_type)?;
+
+  let mut document = env.create_object()?;
+  document.set_named_property("$schema", env.create_string(draft.uri())?)?;
+  for key in JsObject::keys(&root)? {
+    document.set_named_property(&key, root.get_named_property::<JsUnknown>(&key)?)?;
+  }
+
+  let mut defs = env.create_object()?;
+  let mut written = 0;
+  while let Some(&i) = writer.def_order.get(written) {
+    let b_type = writer.shared[i].clone();
+    let name = writer.def_name(i);
+    defs.set_named_property(&name, writer.inline_schema(&b_type)?)?;
+    written += 1;
+  }
+  if written > 0 {
+    document.set_named_property(draft.defs_key(), defs)?;
+  }
+  Ok(document)
+}
+
+/// Writes JSON Schema objects for schemas, the type specific keywords are added by the types.
+pub struct JsonSchemaWriter {
+  env: Env,
+  draft: Draft,
+  /// Container schemas written so far with the amount of times they were written.
+  seen: Vec<(BType, u32)>,
+  /// Only count container schemas, repeated ones are not written again.
+  counting: bool,
+  /// Container schemas used more than once, written once to the definitions.
+  shared: Vec<BType>,
+  /// Names of the shared schemas in the definitions, in the order of `shared`.
+  def_names: Vec<Option<String>>,
+  /// Indexes of the referenced shared schemas, in the order they were first referenced.
+  def_order: Vec<usize>,
+  /// Prefix of `$ref`s to shared schemas.
+  ref_prefix: String,
+  /// Writing OpenAPI components, shared schemas are the named components.
+  components: bool,
+}
+
+impl JsonSchemaWriter {
+  /// Create a writer for the shared schemas, without shared schemas it only counts.
+  fn new(env: Env, draft: Draft, shared: Option<Vec<BType>>) -> Self {
+    let counting = shared.is_none();
+    let shared = shared.unwrap_or_default();
+    JsonSchemaWriter {
+      env,
+      draft,
+      seen: vec![],
+      counting,
+      def_names: vec![None; shared.len()],
+      shared,
+      def_order: vec![],
+      ref_prefix: format!("#/{}/", draft.defs_key()),
+      components: false,
+    }
+  }
+
+  /// Create a writer for named OpenAPI components, which reference each other with `$ref`.
+  fn for_components(env: Env, components: &[(String, BType)]) -> Self {
+    JsonSchemaWriter {
+      env,
+      draft: Draft::Draft2020,
+      seen: vec![],
+      counting: false,
+      shared: components
+        .iter()
+        .map(|(_, b_type)| b_type.clone())
+        .collect(),
+      def_names: components
+        .iter()
+        .map(|(name, _)| Some(name.clone()))
+        .collect(),
+      def_order: vec![],
+      ref_prefix: "#/components/schemas/".to_string(),
+      components: true,
+    }
+  }
+
+  pub fn env(&self) -> Env {
+    self.env
+  }
+
+  pub fn draft(&self) -> Draft {
+    self.draft
+  }
+
+  /// Write the schema of `b_type`, shared schemas are written as `$ref`.
+  pub fn schema(&mut self, b_type: &BType) -> Result<JsObject> {
+    let Some(i) = self.shared_index(b_type) else {
+      return self.inline_schema(b_type);
+    };
+    let name = self.def_name(i);
+    let mut obj = self.env.create_object()?;
+    obj.set_named_property(
+      "$ref",
+      self
+        .env
+        .create_string(&format!("{}{}", self.ref_prefix, name))?,
+    )?;
+
+    // components are referenced by optional, nullable or described variants as well
+    let base = get_btype_base(b_type);
+    let shared_base = get_btype_base(&self.shared[i]);
+    if base.nullable && !shared_base.nullable {
+      obj = self.nullable(obj)?;
+    }
+    if base.meta != shared_base.meta {
+      self.set_metadata(&mut obj, base)?;
+    }
+    Ok(obj)
+  }
+
+  /// `$ref` of a named component matching `b_type`.
+  pub fn component_ref(&self, b_type: &BType) -> Option<String> {
+    match self.components {
+      true => self
+        .shared_index(b_type)
+        .map(|i| format!("{}{}", self.ref_prefix, self.shared_name(i))),
+      false => None,
+    }
+  }
+
+  pub fn components(&self) -> bool {
+    self.components
+  }
+
+  fn shared_index(&self, b_type: &BType) -> Option<usize> {
+    match self.components {
+      true => self
+        .shared
+        .iter()
+        .position(|shared| is_variant_of(b_type, shared)),
+      false => self.shared.iter().position(|shared| shared == b_type),
+    }
+  }
+
+  fn shared_name(&self, i: usize) -> &str {
+    self.def_names[i].as_deref().unwrap_or_default()
+  }
+
+  pub fn schemas<'a>(
+    &mut self,
+    b_types: impl ExactSizeIterator<Item = &'a BType>,
+  ) -> Result<JsObject> {
+    let mut array = self.env.create_array_with_length(b_types.len())?;
+    for (i, b_type) in b_types.enumerate() {
+      let schema = self
+        .schema(b_type)
+        .map_err(|err| with_path(err, &i.to_string()))?;
+      array.set_element(i as u32, schema)?;
+    }
+    Ok(array)
+  }
+
+  /// Write the schema of `b_type` itself, even when it is shared.
+  fn inline_schema(&mut self, b_type: &BType) -> Result<JsObject> {
+    if self.counting && is_container(b_type) {
+      match self.seen.iter_mut().find(|(seen, _)| seen == b_type) {
+        Some((_, count)) => {
+          *count += 1;
+          return Ok(self.env.create_object()?);
+        }
+        None => self.seen.push((b_type.clone(), 1)),
+      }
+    }
+
+    let mut obj = match b_type {
+      BType::Undefined(s) => s.json_schema(self),
+      BType::Null(s) => s.json_schema(self),
+      BType::Boolean(s) => s.json_schema(self),
+      BType::Number(s) => s.json_schema(self),
+      BType::String(s) => s.json_schema(self),
+      BType::Enum(s) => s.json_schema(self),
+      BType::Array(s) => s.json_schema(self),
+      BType::Object(s) => s.json_schema(self),
+      BType::Instance(s) => s.json_schema(self),
+      BType::Union(s) => s.json_schema(self),
+      BType::Tuple(s) => s.json_schema(self),
+      BType::Conditional(s) => s.json_schema(self),
+    }?;
+
+    let base = get_btype_base(b_type);
+    if base.nullable {
+      obj = self.nullable(obj)?;
+    }
+    self.set_metadata(&mut obj, base)?;
+    Ok(obj)
+  }
+
+  /// Allow null for a schema, using a type array when the schema has a single type.
+  fn nullable(&self, mut obj: JsObject) -> Result<JsObject> {
+    let single_type = obj.has_named_property("type")?
+      && !obj.has_named_property("enum")?
+      && !obj.has_named_property("const")?
+      && obj.get_named_property::<JsUnknown>("type")?.get_type()? == ValueType::String;
+    if single_type {
+      let type_name = obj.get_named_property::<JsString>("type")?;
+      if type_name.into_utf8()?.as_str()? == "null" {
+        return Ok(obj);
+      }
+      let mut types = self.env.create_array_with_length(2)?;
+      types.set_element(0, type_name)?;
+      types.set_element(1, self.env.create_string("null")?)?;
+      obj.set_named_property("type", types)?;
+      return Ok(obj);
+    }
+
+    let mut any_of = self.env.create_array_with_length(2)?;
+    any_of.set_element(0, obj)?;
+    any_of.set_element(1, self.typed("null")?)?;
+    let mut nullable = self.env.create_object()?;
+    nullable.set_named_property("anyOf", any_of)?;
+    Ok(nullable)
+  }
+
+  fn set_metadata(&self, obj: &mut JsObject, base: &BValueBase) -> Result<()> {
+    let meta = &base.meta;
+    if let Some(title) = &meta.title {
+      obj.set_named_property("title", self.env.create_string(title)?)?;
+    }
+    if let Some(description) = &meta.description {
+      obj.set_named_property("description", self.env.create_string(description)?)?;
+    }
+    if let Some(examples) = &meta.examples {
+      obj.set_named_property("examples", examples.get::<JsObject>(self.env)?)?;
+    }
+    if meta.deprecated.is_some() && self.draft == Draft::Draft2020 {
+      obj.set_named_property("deprecated", self.env.get_boolean(true)?)?;
+    }
+    if let (true, Some(custom)) = (self.components, &meta.custom) {
+      // access of component properties is set with `.meta({ readOnly: true })`
+      let custom = custom.get::<JsObject>(self.env)?;
+      for key in ["readOnly", "writeOnly"] {
+        let value = custom.get_named_property::<JsUnknown>(key)?;
+        if value.get_type()? == ValueType::Boolean && js_bool(&value)? {
+          obj.set_named_property(key, value)?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Write an object of schemas, keys are sorted to make documents deterministic.
+  pub fn schema_map<'a>(
+    &mut self,
+    b_types: impl Iterator<Item = (&'a String, &'a BType)>,
+  ) -> Result<JsObject> {
+    let mut b_types: Vec<_> = b_types.collect();
+    b_types.sort_by_key(|(key, _)| *key);
+
+    let mut obj = self.env.create_object()?;
+    for (key, b_type) in b_types {
+      let schema = self.schema(b_type).map_err(|err| with_path(err, key))?;
+      obj.set_named_property(key, schema)?;
+    }
+    Ok(obj)
+  }
+
+  /// Create a schema object with the `type` keyword.
+  pub fn typed(&self, type_name: &str) -> Result<JsObject> {
+    let mut obj = self.env.create_object()?;
+    obj.set_named_property("type", self.env.create_string(type_name)?)?;
+    Ok(obj)
+  }
+
+  pub fn set_u32(&self, obj: &mut JsObject, key: &str, value: Option<u32>) -> Result<()> {
+    match value {
+      Some(value) => Ok(obj.set_named_property(key, self.env.create_uint32(value)?)?),
+      None => Ok(()),
+    }
+  }
+
+  pub fn set_f64(&self, obj: &mut JsObject, key: &str, value: Option<f64>) -> Result<()> {
+    match value {
+      Some(value) => Ok(obj.set_named_property(key, self.env.create_double(value)?)?),
+      None => Ok(()),
+    }
+  }
+
+  /// Name of a shared schema in the definitions, its title when usable in a `$ref`.
+  fn def_name(&mut self, i: usize) -> String {
+    if let Some(name) = &self.def_names[i] {
+      return name.clone();
+    }
+
+    let title = get_btype_base(&self.shared[i]).meta.title.clone();
+    let name = match title {
+      Some(title)
+        if is_ref_safe(&title)
+          && !self
+            .def_names
+            .iter()
+            .any(|name| name.as_ref() == Some(&title)) =>
+      {
+        title
+      }
+      _ => format!("Schema{}", self.def_order.len() + 1),
+    };
+    self.def_names[i] = Some(name.clone());
+    self.def_order.push(i);
+    name
+  }
+}
+
+/// Create the `components` object of an OpenAPI 3.1 document for named schemas.
+pub fn to_openapi_components(env: Env, components: Vec<(String, BType)>) -> Result<JsObject> {
+  if let Some((name, _)) = components.iter().find(|(name, _)| !is_ref_safe(name)) {
+    return Err(invalid_arg(
+      format!(
+        "Component name '{}' can only contain letters, digits, '.', '-' and '_'",
+        name
+      )
+      .as_str(),
+    ));
+  }
+
+  let mut writer = JsonSchemaWriter::for_components(env, &components);
+  let mut schemas = env.create_object()?;
+  for (name, b_type) in &components {
+    let schema = writer
+      .inline_schema(b_type)
+      .map_err(|err| with_path(err, name))?;
+    schemas.set_named_property(name, schema)?;
+  }
+
+  let mut obj = env.create_object()?;
+  obj.set_named_property("schemas", schemas)?;
+  Ok(obj)
+}
+
+/// Whether `b_type` is `component` or an optional, nullable or described variant of it.
+fn is_variant_of(b_type: &BType, component: &BType) -> bool {
+  let strip = |b_type: &BType| {
+    let mut b_type = b_type.clone();
+    let base = get_btype_base_mut(&mut b_type);
+    base.optional = false;
+    base.nullable = false;
+    base.meta = Metadata::default();
+    b_type
+  };
+  // a nullable component can not be referenced for values which may not be null
+  (get_btype_base(b_type).nullable || !get_btype_base(component).nullable)
+    && strip(b_type) == strip(component)
+}
+
+/// Schemas which are worth sharing through the definitions.
+fn is_container(b_type: &BType) -> bool {
+  matches!(
+    b_type,
+    BType::Object(_) | BType::Union(_) | BType::Tuple(_) | BType::Conditional(_)
+  )
+}
+
+fn is_ref_safe(name: &str) -> bool {
+  !name.is_empty()
+    && name
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+/// Keywords without effect on validation, which are accepted and ignored.
+const IGNORED_KEYWORDS: [&str; 8] = [
+  "$schema",
+  "$id",
+  "$comment",
+  "$defs",
+  "definitions",
+  "default",
+  "readOnly",
+  "writeOnly",
+];
+
+/// Keywords describing a schema, the first one is kept when merging schemas.
+const ANNOTATIONS: [&str; 4] = ["title", "description", "examples", "deprecated"];
+
+/// Translate a JSON Schema document to a definition for `fromDefinition()`.
+/// Keywords which can not be represented exactly are rejected instead of ignored.
+pub fn definition_from_json_schema(env: Env, schema: JsUnknown) -> Result<JsObject> {
+  let root = ClonableJsUnknown::new(env, schema)?;
+  let mut compiler = JsonSchemaCompiler {
+    env,
+    root,
+    refs: vec![],
+  };
+  let schema = compiler.root.create_clone()?;
+  compiler.compile(schema, None)
+}
+
+struct JsonSchemaCompiler {
+  env: Env,
+  root: ClonableJsUnknown,
+  /// `$ref`s being compiled, to reject recursive schemas.
+  refs: Vec<String>,
+}
+
+impl JsonSchemaCompiler {
+  /// Compile a schema to a definition, `default_type` is used when the schema has no `type`.
+  fn compile(&mut self, schema: JsUnknown, default_type: Option<&str>) -> Result<JsObject> {
+    let obj = schema_object(schema)?;
+    if obj.has_named_property("$ref")? || obj.has_named_property("allOf")? {
+      let depth = self.refs.len();
+      let compiled = self
+        .flatten(obj)
+        .and_then(|merged| self.compile(merged.into_unknown(), default_type));
+      self.refs.truncate(depth);
+      return compiled;
+    }
+
+    let mut reader = DefinitionReader::new(self.env, obj.into_unknown())?;
+    for keyword in IGNORED_KEYWORDS {
+      reader.get(keyword)?;
+    }
+    let mut definition = self.compile_keywords(&mut reader, default_type)?;
+
+    if let Some(title) = reader.str("title")? {
+      definition.set_named_property("title", self.env.create_string(&title)?)?;
+    }
+    if let Some(description) = reader.str("description")? {
+      definition.set_named_property("description", self.env.create_string(&description)?)?;
+    }
+    if let Some(examples) = reader.array_object("examples")? {
+      definition.set_named_property("examples", examples)?;
+    }
+    if reader.flag("deprecated")? {
+      definition.set_named_property("deprecated", self.env.create_string("")?)?;
+    }
+
+    match reader.unread_key()? {
+      Some(keyword) => Err(invalid_arg(
+        format!("Unsupported keyword '{}'", keyword).as_str(),
+      )),
+      None => Ok(definition),
+    }
+  }
+
+  fn compile_keywords(
+    &mut self,
+    reader: &mut DefinitionReader,
+    default_type: Option<&str>,
+  ) -> Result<JsObject> {
+    if let Some(options) = reader.array("anyOf")? {
+      return self.union(options, "anyOf", false);
+    }
+    if let Some(options) = reader.array("oneOf")? {
+      return self.union(options, "oneOf", true);
+    }
+    if let Some(value) = reader.get("const")? {
+      return self.constant(value).map_err(|err| with_path(err, "const"));
+    }
+    if let Some(values) = reader.array("enum")? {
+      // values have to be valid for both keywords, values of other types are left out
+      let Some(types) = read_types(reader)? else {
+        return self.enumeration(values);
+      };
+      let mut typed = vec![];
+      for value in values {
+        if has_json_type(&value, &types)? {
+          typed.push(value);
+        }
+      }
+      if typed.is_empty() {
+        return Err(with_path(invalid_arg("No values match 'type'"), "enum"));
+      }
+      return self.enumeration(typed);
+    }
+
+    let types = match read_types(reader)? {
+      None => match default_type {
+        Some(default_type) => vec![default_type.to_string()],
+        None => {
+          return Err(invalid_arg(
+            "Schemas without 'type' accept values of any type, which is not supported",
+          ))
+        }
+      },
+      Some(types) => types,
+    };
+
+    let mut definitions = vec![];
+    for type_name in types.iter().filter(|type_name| *type_name != "null") {
+      definitions.push(self.typed(reader, type_name)?);
+    }
+    let nullable = types.iter().any(|type_name| type_name == "null");
+    let mut definition = match definitions.len() {
+      0 if nullable => return type_definition(self.env, "null"),
+      0 => return Err(with_path(invalid_arg("No types given"), "type")),
+      1 => definitions.remove(0),
+      _ => union_definition(self.env, definitions, false)?,
+    };
+    if nullable {
+      definition.set_named_property("nullable", self.env.get_boolean(true)?)?;
+    }
+    Ok(definition)
+  }
+
+  fn typed(&mut self, reader: &mut DefinitionReader, type_name: &str) -> Result<JsObject> {
+    match type_name {
+      "string" => self.string(reader),
+      "number" | "integer" => self.number(reader, type_name == "integer"),
+      "boolean" => type_definition(self.env, "boolean"),
+      "array" => self.array(reader),
+      "object" => self.object(reader),
+      _ => Err(with_path(
+        invalid_arg(format!("Unknown type '{}'", type_name).as_str()),
+        "type",
+      )),
+    }
+  }
+
+  fn string(&mut self, reader: &mut DefinitionReader) -> Result<JsObject> {
+    let env = self.env;
+    let mut definition = type_definition(env, "string")?;
+    let (min, max) = (reader.u32("minLength")?, reader.u32("maxLength")?);
+    check_range("minLength", min, "maxLength", max)?;
+    set_u32(env, &mut definition, "min", min)?;
+    set_u32(env, &mut definition, "max", max)?;
+
+    if let Some(pattern) = reader.str("pattern")? {
+      Pattern::new(&pattern).map_err(|err| with_path(err, "pattern"))?;
+      definition.set_named_property("pattern", env.create_string(&pattern)?)?;
+    }
+    if let Some(format) = reader.str("format")? {
+      StringFormat::from_name(&format).map_err(|err| with_path(err, "format"))?;
+      definition.set_named_property("format", env.create_string(&format)?)?;
+    }
+    Ok(definition)
+  }
+
+  fn number(&mut self, reader: &mut DefinitionReader, int: bool) -> Result<JsObject> {
+    let env = self.env;
+    let mut definition = type_definition(env, "number")?;
+    let (min, max) = (reader.f64("minimum")?, reader.f64("maximum")?);
+    check_range("minimum", min, "maximum", max)?;
+    if let Some(min) = min {
+      definition.set_named_property("min", env.create_double(min)?)?;
+    }
+    if let Some(max) = max {
+      definition.set_named_property("max", env.create_double(max)?)?;
+    }
+    if int {
+      definition.set_named_property("int", env.get_boolean(true)?)?;
+    }
+    Ok(definition)
+  }
+
+  fn array(&mut self, reader: &mut DefinitionReader) -> Result<JsObject> {
+    let env = self.env;
+    let (min, max) = (reader.u32("minItems")?, reader.u32("maxItems")?);
+    check_range("minItems", min, "maxItems", max)?;
+
+    let items = reader.get("items")?;
+    let (elements, elements_key, rest, rest_key) = match reader.array("prefixItems")? {
+      Some(elements) => (elements, "prefixItems", items, "items"),
+      None => match items {
+        Some(items) if items.is_array()? => {
+          let elements = array_elements(unsafe { items.cast::<JsObject>() })?;
+          (
+            elements,
+            "items",
+            reader.get("additionalItems")?,
+            "additionalItems",
+          )
+        }
+        Some(items) => return self.list(reader, items, min, max),
+        None => {
+          return Err(invalid_arg(
+            "Arrays without 'items' accept any elements, which is not supported",
+          ))
+        }
+      },
+    };
+
+    let length = elements.len() as u32;
+    let min = min.unwrap_or(0);
+    if min > length {
+      return Err(with_path(
+        invalid_arg("'minItems' higher than the amount of tuple elements is not supported"),
+        "minItems",
+      ));
+    }
+
+    let mut definitions = env.create_array_with_length(elements.len())?;
+    for (i, element) in elements.into_iter().enumerate() {
+      let mut definition = self
+        .compile(element, None)
+        .map_err(|err| with_path(with_path(err, &i.to_string()), elements_key))?;
+      // elements after `minItems` may be missing
+      if i as u32 >= min {
+        definition.set_named_property("optional", env.get_boolean(true)?)?;
+      }
+      definitions.set_element(i as u32, definition)?;
+    }
+
+    let rest = match rest {
+      Some(rest) if rest.get_type()? != ValueType::Boolean => Some(
+        self
+          .compile(rest, None)
+          .map_err(|err| with_path(err, rest_key))?,
+      ),
+      Some(rest) if !js_bool(&rest)? => None,
+      _ => {
+        return Err(with_path(
+          invalid_arg("Tuples accepting any additional items are not supported, set it to false"),
+          rest_key,
+        ))
+      }
+    };
+    if let Some(max) = max {
+      if rest.is_some() || max < length {
+        return Err(with_path(
+          invalid_arg("'maxItems' restricting tuple elements is not supported"),
+          "maxItems",
+        ));
+      }
+    }
+
+    let mut definition = type_definition(env, "tuple")?;
+    definition.set_named_property("elements", definitions)?;
+    if let Some(rest) = rest {
+      definition.set_named_property("rest", rest)?;
+    }
+    Ok(definition)
+  }
+
+  /// Array with a single schema for all elements.
+  fn list(
+    &mut self,
+    reader: &mut DefinitionReader,
+    items: JsUnknown,
+    min: Option<u32>,
+    max: Option<u32>,
+  ) -> Result<JsObject> {
+    let env = self.env;
+    let mut definition = type_definition(env, "array")?;
+    let element = self
+      .compile(items, None)
+      .map_err(|err| with_path(err, "items"))?;
+    definition.set_named_property("element", element)?;
+    set_u32(env, &mut definition, "min", min)?;
+    set_u32(env, &mut definition, "max", max)?;
+    if reader.flag("uniqueItems")? {
+      definition.set_named_property("unique", env.get_boolean(true)?)?;
+    }
+
+    if let Some(contains) = reader.get("contains")? {
+      let (min, max) = (reader.u32("minContains")?, reader.u32("maxContains")?);
+      check_range("minContains", min, "maxContains", max)?;
+      let mut obj = env.create_object()?;
+      let schema = self
+        .compile(contains, None)
+        .map_err(|err| with_path(err, "contains"))?;
+      obj.set_named_property("schema", schema)?;
+      set_u32(env, &mut obj, "min", min)?;
+      set_u32(env, &mut obj, "max", max)?;
+      definition.set_named_property("contains", obj)?;
+    }
+    Ok(definition)
+  }
+
+  fn object(&mut self, reader: &mut DefinitionReader) -> Result<JsObject> {
+    let env = self.env;
+    let mut definition = type_definition(env, "object")?;
+    let required = reader.strings("required")?.unwrap_or_default();
+
+    let mut shape = env.create_object()?;
+    if let Some(properties) = reader.object("properties")? {
+      for key in JsObject::keys(&properties)? {
+        let mut property = self
+          .compile(properties.get_named_property::<JsUnknown>(&key)?, None)
+          .map_err(|err| with_path(with_path(err, &key), "properties"))?;
+        if !required.contains(&key) {
+          property.set_named_property("optional", env.get_boolean(true)?)?;
+        }
+        shape.set_named_property(&key, property)?;
+      }
+    }
+    for key in &required {
+      if !shape.has_named_property(key)? {
+        return Err(with_path(
+          invalid_arg(
+            format!(
+              "Required key '{}' without a schema in 'properties' is not supported",
+              key
+            )
+            .as_str(),
+          ),
+          "required",
+        ));
+      }
+    }
+    definition.set_named_property("shape", shape)?;
+
+    // additional properties are allowed by default and kept, not removed
+    match reader.get("additionalProperties")? {
+      Some(value) if value.get_type()? == ValueType::Boolean && !js_bool(&value)? => {
+        definition.set_named_property("strict", env.get_boolean(true)?)?;
+      }
+      Some(value) if value.get_type()? == ValueType::Boolean => {
+        definition.set_named_property("passthrough", env.get_boolean(true)?)?;
+      }
+      Some(value) => {
+        let catchall = self
+          .compile(value, None)
+          .map_err(|err| with_path(err, "additionalProperties"))?;
+        definition.set_named_property("catchall", catchall)?;
+      }
+      None => {
+        definition.set_named_property("passthrough", env.get_boolean(true)?)?;
+      }
+    }
+
+    if let Some(patterns) = reader.object("patternProperties")? {
+      let mut definitions = env.create_object()?;
+      for pattern in JsObject::keys(&patterns)? {
+        let compiled = Pattern::new(&pattern)
+          .and_then(|_| self.compile(patterns.get_named_property::<JsUnknown>(&pattern)?, None));
+        let schema = compiled.map_err(|err| with_path(err, "patternProperties"))?;
+        definitions.set_named_property(&pattern, schema)?;
+      }
+      definition.set_named_property("patternProperties", definitions)?;
+    }
+    if let Some(property_names) = reader.get("propertyNames")? {
+      // keys are always strings, the type can be left out
+      let schema = self
+        .compile(property_names, Some("string"))
+        .map_err(|err| with_path(err, "propertyNames"))?;
+      definition.set_named_property("propertyNames", schema)?;
+    }
+
+    let (min, max) = (reader.u32("minProperties")?, reader.u32("maxProperties")?);
+    check_range("minProperties", min, "maxProperties", max)?;
+    set_u32(env, &mut definition, "minProperties", min)?;
+    set_u32(env, &mut definition, "maxProperties", max)?;
+    Ok(definition)
+  }
+
+  fn union(&mut self, options: Vec<JsUnknown>, keyword: &str, exclusive: bool) -> Result<JsObject> {
+    if options.is_empty() {
+      return Err(with_path(invalid_arg("No schemas given"), keyword));
+    }
+    let mut definitions = vec![];
+    for (i, option) in options.into_iter().enumerate() {
+      let definition = self
+        .compile(option, None)
+        .map_err(|err| with_path(with_path(err, &i.to_string()), keyword))?;
+      definitions.push(definition);
+    }
+    union_definition(self.env, definitions, exclusive)
+  }
+
+  fn constant(&self, value: JsUnknown) -> Result<JsObject> {
+    let env = self.env;
+    match value.get_type()? {
+      ValueType::String => {
+        let mut definition = type_definition(env, "enum")?;
+        let mut values = env.create_array_with_length(1)?;
+        values.set_element(0, value)?;
+        definition.set_named_property("values", values)?;
+        Ok(definition)
+      }
+      ValueType::Number => {
+        let mut definition = type_definition(env, "number")?;
+        let n = value.coerce_to_number()?.get_double()?;
+        definition.set_named_property("min", env.create_double(n)?)?;
+        definition.set_named_property("max", env.create_double(n)?)?;
+        Ok(definition)
+      }
+      ValueType::Boolean => {
+        let mut definition = type_definition(env, "boolean")?;
+        definition.set_named_property("value", value)?;
+        Ok(definition)
+      }
+      ValueType::Null => type_definition(env, "null"),
+      value_type => Err(invalid_arg(
+        format!("Constant values of type {} are not supported", value_type).as_str(),
+      )),
+    }
+  }
+
+  /// Strings are combined to a single enum, other values become constants of a union.
+  fn enumeration(&self, values: Vec<JsUnknown>) -> Result<JsObject> {
+    if values.is_empty() {
+      return Err(with_path(invalid_arg("No values given"), "enum"));
+    }
+
+    let mut strings = vec![];
+    let mut options = vec![];
+    let mut nullable = false;
+    for (i, value) in values.into_iter().enumerate() {
+      match value.get_type()? {
+        ValueType::String => strings.push(value),
+        ValueType::Null => nullable = true,
+        _ => options.push(
+          self
+            .constant(value)
+            .map_err(|err| with_path(with_path(err, &i.to_string()), "enum"))?,
+        ),
+      }
+    }
+    if !strings.is_empty() {
+      let mut definition = type_definition(self.env, "enum")?;
+      let mut array = self.env.create_array_with_length(strings.len())?;
+      for (i, value) in strings.into_iter().enumerate() {
+        array.set_element(i as u32, value)?;
+      }
+      definition.set_named_property("values", array)?;
+      options.insert(0, definition);
+    }
+
+    let mut definition = match options.len() {
+      0 => return type_definition(self.env, "null"),
+      1 => options.remove(0),
+      _ => union_definition(self.env, options, false)?,
+    };
+    if nullable {
+      definition.set_named_property("nullable", self.env.get_boolean(true)?)?;
+    }
+    Ok(definition)
+  }
+
+  /// Merge the schemas of `$ref` and `allOf` into the schema itself.
+  fn flatten(&mut self, obj: JsObject) -> Result<JsObject> {
+    let env = self.env;
+    // the schema itself comes first to keep its annotations
+    let mut own = env.create_object()?;
+    for key in JsObject::keys(&obj)? {
+      if key != "$ref" && key != "allOf" {
+        own.set_named_property(&key, obj.get_named_property::<JsUnknown>(&key)?)?;
+      }
+    }
+    let mut members = vec![("", own)];
+    // each member only sees its own chain of `$ref`s, the merged schema is compiled with all of them
+    let depth = self.refs.len();
+    let mut chains = vec![];
+
+    if obj.has_named_property("$ref")? {
+      let reference = obj.get_named_property::<JsUnknown>("$ref")?;
+      if reference.get_type()? != ValueType::String {
+        return Err(with_path(
+          invalid_arg(format!("expect String, got: {}", reference.get_type()?).as_str()),
+          "$ref",
+        ));
+      }
+      let reference = js_string(reference)?;
+      if self.refs.contains(&reference) {
+        return Err(with_path(
+          invalid_arg(format!("Recursive $ref '{}' is not supported", reference).as_str()),
+          "$ref",
+        ));
+      }
+      let target = self
+        .resolve(&reference)
+        .map_err(|err| with_path(err, "$ref"))?;
+      self.refs.push(reference);
+      let member = self.flatten_member(target);
+      chains.extend(self.refs.split_off(depth));
+      members.push(("$ref", member?));
+    }
+
+    if obj.has_named_property("allOf")? {
+      let all_of = obj.get_named_property::<JsUnknown>("allOf")?;
+      if !all_of.is_array()? {
+        return Err(with_path(
+          invalid_arg(format!("expect Array, got: {}", all_of.get_type()?).as_str()),
+          "allOf",
+        ));
+      }
+      for (i, member) in array_elements(unsafe { all_of.cast::<JsObject>() })?
+        .into_iter()
+        .enumerate()
+      {
+        let member = self.flatten_member(member);
+        chains.extend(self.refs.split_off(depth));
+        let member = member.map_err(|err| with_path(with_path(err, &i.to_string()), "allOf"))?;
+        members.push(("allOf", member));
+      }
+    }
+    self.refs.extend(chains);
+
+    // additional properties of a schema are checked against its own properties only
+    let with_properties = members
+      .iter()
+      .map(|(_, member)| member.has_named_property("properties"))
+      .collect::<napi::Result<Vec<_>>>()?;
+    for (i, (path, member)) in members.iter().enumerate() {
+      let others_have_properties = with_properties
+        .iter()
+        .enumerate()
+        .any(|(j, has_properties)| i != j && *has_properties);
+      if member.has_named_property("additionalProperties")? && others_have_properties {
+        let error = invalid_arg(
+          "'additionalProperties' combined with properties of other schemas is not supported",
+        );
+        return Err(match path.is_empty() {
+          true => error,
+          false => with_path(error, path),
+        });
+      }
+    }
+
+    let mut merged = env.create_object()?;
+    for (path, member) in members {
+      merge_schema(env, &mut merged, member).map_err(|err| match path.is_empty() {
+        true => err,
+        false => with_path(err, path),
+      })?;
+    }
+    Ok(merged)
+  }
+
+  fn flatten_member(&mut self, member: JsUnknown) -> Result<JsObject> {
+    let obj = schema_object(member)?;
+    match obj.has_named_property("$ref")? || obj.has_named_property("allOf")? {
+      true => self.flatten(obj),
+      false => Ok(obj),
+    }
+  }
+
+  /// Resolve a local `$ref` like `#/$defs/Address` in the document.
+  fn resolve(&self, reference: &str) -> Result<JsUnknown> {
+    let not_found =
+      || invalid_arg(format!("$ref '{}' does not point to a schema", reference).as_str());
+    let Some(pointer) = reference.strip_prefix('#') else {
+      return Err(invalid_arg(
+        format!("Only local $ref is supported, got: '{}'", reference).as_str(),
+      ));
+    };
+
+    let mut value = self.root.create_clone()?;
+    if pointer.is_empty() {
+      return Ok(value);
+    }
+    let Some(pointer) = pointer.strip_prefix('/') else {
+      return Err(not_found());
+    };
+    for token in pointer.split('/') {
+      let token = percent_decode(token)
+        .ok_or_else(not_found)?
+        .replace("~1", "/")
+        .replace("~0", "~");
+      if value.get_type()? != ValueType::Object {
+        return Err(not_found());
+      }
+      value = unsafe { value.cast::<JsObject>() }.get_named_property::<JsUnknown>(&token)?;
+    }
+
+    match value.get_type()? {
+      ValueType::Object | ValueType::Boolean => Ok(value),
+      _ => Err(not_found()),
+    }
+  }
+}
+
+/// Merge the keywords of a schema into `target`, keywords of both have to be equal.
+/// Properties of both are combined with `allOf`, required keys are combined.
+fn merge_schema(env: Env, target: &mut JsObject, source: JsObject) -> Result<()> {
+  for key in JsObject::keys(&source)? {
+    let value = source.get_named_property::<JsUnknown>(&key)?;
+    let existing = target.get_named_property::<JsUnknown>(&key)?;
+    let exists = existing.get_type()? != ValueType::Undefined;
+
+    match key.as_str() {
+      "properties" => {
+        let mut properties = env.create_object()?;
+        let mut sources = vec![value];
+        if exists {
+          sources.insert(0, existing);
+        }
+        for source in sources {
+          let source = schema_object(source).map_err(|err| with_path(err, "properties"))?;
+          for name in JsObject::keys(&source)? {
+            let schema = source.get_named_property::<JsUnknown>(&name)?;
+            let current = properties.get_named_property::<JsUnknown>(&name)?;
+            if current.get_type()? == ValueType::Undefined {
+              properties.set_named_property(&name, schema)?;
+              continue;
+            }
+            let mut all_of = env.create_array_with_length(2)?;
+            all_of.set_element(0, current)?;
+            all_of.set_element(1, schema)?;
+            let mut combined = env.create_object()?;
+            combined.set_named_property("allOf", all_of)?;
+            properties.set_named_property(&name, combined)?;
+          }
+        }
+        target.set_named_property("properties", properties)?;
+      }
+      "required" if exists => {
+        let mut keys = vec![];
+        for source in [existing, value] {
+          if !source.is_array()? {
+            return Err(with_path(invalid_arg("expect Array"), "required"));
+          }
+          for key in array_elements(unsafe { source.cast::<JsObject>() })? {
+            let key = js_string(key)?;
+            if !keys.contains(&key) {
+              keys.push(key);
+            }
+          }
+        }
+        let mut array = env.create_array_with_length(keys.len())?;
+        for (i, key) in keys.iter().enumerate() {
+          array.set_element(i as u32, env.create_string(key)?)?;
+        }
+        target.set_named_property("required", array)?;
+      }
+      _ if !exists => target.set_named_property(&key, value)?,
+      _ if ANNOTATIONS.contains(&key.as_str()) || IGNORED_KEYWORDS.contains(&key.as_str()) => {}
+      _ if env.strict_equals(existing, value)? => {}
+      _ => {
+        return Err(invalid_arg(
+          format!("Conflicting keyword '{}' in allOf is not supported", key).as_str(),
+        ))
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Get a schema as object, boolean schemas accepting everything or nothing are not supported.
+fn schema_object(schema: JsUnknown) -> Result<JsObject> {
+  match schema.get_type()? {
+    ValueType::Boolean => Err(invalid_arg("Boolean schemas are not supported")),
+    ValueType::Object if !schema.is_array()? => Ok(unsafe { schema.cast::<JsObject>() }),
+    ValueType::Object => Err(invalid_arg("expect Object, got: Array")),
+    value_type => Err(invalid_arg(
+      format!("expect Object, got: {}", value_type).as_str(),
+    )),
+  }
+}
+
+/// Read the `type` keyword, a type name or an array of them.
+fn read_types(reader: &mut DefinitionReader) -> Result<Option<Vec<String>>> {
+  match reader.get("type")? {
+    None => Ok(None),
+    Some(value) if value.get_type()? == ValueType::String => Ok(Some(vec![js_string(value)?])),
+    Some(_) => reader.strings("type"),
+  }
+}
+
+/// Whether a value is an instance of one of the JSON Schema `types`.
+fn has_json_type(value: &JsUnknown, types: &[String]) -> Result<bool> {
+  let value_type = value.get_type()?;
+  for type_name in types {
+    let matches = match (value_type, type_name.as_str()) {
+      (ValueType::String, "string")
+      | (ValueType::Number, "number")
+      | (ValueType::Boolean, "boolean")
+      | (ValueType::Null, "null") => true,
+      (ValueType::Number, "integer") => {
+        let n = unsafe { value.cast::<JsNumber>() }.get_double()?;
+        n.is_finite() && n.fract() == 0.0
+      }
+      (ValueType::Object, "array") => value.is_array()?,
+      (ValueType::Object, "object") => !value.is_array()?,
+      _ => false,
+    };
+    if matches {
+      return Ok(true);
+    }
+  }
+  Ok(false)
+}
+
+fn js_bool(value: &JsUnknown) -> Result<bool> {
+  Ok(unsafe { value.cast::<JsBoolean>() }.get_value()?)
+}
+
+fn array_elements(array: JsObject) -> Result<Vec<JsUnknown>> {
+  Ok(
+    (0..array.get_array_length()?)
+      .map(|i| array.get_element::<JsUnknown>(i))
+      .collect::<napi::Result<_>>()?,
+  )
+}
+
+pub fn type_definition(env: Env, type_name: &str) -> Result<JsObject> {
+  let mut definition = env.create_object()?;
+  definition.set_named_property("type", env.create_string(type_name)?)?;
+  Ok(definition)
+}
+
+fn union_definition(env: Env, options: Vec<JsObject>, exclusive: bool) -> Result<JsObject> {
+  let mut definition = type_definition(env, "union")?;
+  let mut array = env.create_array_with_length(options.len())?;
+  for (i, option) in options.into_iter().enumerate() {
+    array.set_element(i as u32, option)?;
+  }
+  definition.set_named_property("options", array)?;
+  if exclusive {
+    definition.set_named_property("exclusive", env.get_boolean(true)?)?;
+  }
+  Ok(definition)
+}
+
+fn set_u32(env: Env, obj: &mut JsObject, key: &str, value: Option<u32>) -> Result<()> {
+  match value {
+    Some(value) => Ok(obj.set_named_property(key, env.create_uint32(value)?)?),
+    None => Ok(()),
+  }
+}
+
+/// Decode `%XX` escapes of a JSON pointer in a URI fragment.
+fn percent_decode(value: &str) -> Option<String> {
+  let bytes = value.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    match bytes[i] {
+      b'%' => {
+        let hex = value.get(i + 1..i + 3)?;
+        decoded.push(u8::from_str_radix(hex, 16).ok()?);
+        i += 3;
+      }
+      byte => {
+        decoded.push(byte);
+        i += 1;
+      }
+    }
+  }
+  String::from_utf8(decoded).ok()
+}
@@ -0,0 +1,367 @@
+use napi::{Env, JsObject, JsUnknown, ValueType};
+
+use crate::{
+  common::{get_btype_base, invalid_arg, with_path, ClonableJsUnknown},
+  definition::DefinitionReader,
+  error::Result,
+  json_schema::type_definition,
+  BType, BValueBase,
+};
+
+/// Create a JSON Type Definition (RFC 8927) schema for `b_type`.
+pub fn to_jtd(env: Env, b_type: &BType) -> Result<JsObject> {
+  JtdWriter { env }.schema(b_type)
+}
+
+/// Writes JTD schemas for schemas, the forms of containers are written by the types.
+pub struct JtdWriter {
+  env: Env,
+}
+
+impl JtdWriter {
+  pub fn env(&self) -> Env {
+    self.env
+  }
+
+  pub fn schema(&mut self, b_type: &BType) -> Result<JsObject> {
+    let mut obj = match b_type {
+      BType::Boolean(s) => s.jtd(self),
+      BType::Number(s) => s.jtd(self),
+      BType::String(s) => s.jtd(self),
+      BType::Enum(s) => {
+        let mut obj = self.env.create_object()?;
+        let options = s.options();
+        let mut values = self.env.create_array_with_length(options.len())?;
+        for (i, value) in options.iter().enumerate() {
+          values.set_element(i as u32, self.env.create_string(value)?)?;
+        }
+        obj.set_named_property("enum", values)?;
+        Ok(obj)
+      }
+      BType::Array(s) => s.jtd(self),
+      BType::Object(s) => s.jtd(self),
+      BType::Union(s) => s.jtd(self),
+      BType::Undefined(_) => Err(invalid_arg("Undefined can not be represented in JTD")),
+      BType::Null(_) => Err(invalid_arg("Null can not be represented in JTD")),
+      BType::Instance(_) => Err(invalid_arg("Instances can not be represented in JTD")),
+      BType::Tuple(_) => Err(invalid_arg("Tuples can not be represented in JTD")),
+      BType::Conditional(_) => Err(invalid_arg("Conditionals can not be represented in JTD")),
+    }?;
+
+    let base = get_btype_base(b_type);
+    if base.nullable {
+      obj.set_named_property("nullable", self.env.get_boolean(true)?)?;
+    }
+    self.set_metadata(&mut obj, base)?;
+    Ok(obj)
+  }
+
+  /// Write an object of schemas, keys are sorted to make schemas deterministic.
+  pub fn schema_map<'a>(
+    &mut self,
+    b_types: impl Iterator<Item = (&'a String, &'a BType)>,
+  ) -> Result<JsObject> {
+    let mut b_types: Vec<_> = b_types.collect();
+    b_types.sort_by_key(|(key, _)| *key);
+
+    let mut obj = self.env.create_object()?;
+    for (key, b_type) in b_types {
+      let schema = self.schema(b_type).map_err(|err| with_path(err, key))?;
+      obj.set_named_property(key, schema)?;
+    }
+    Ok(obj)
+  }
+
+  /// Create a schema of the type form.
+  pub fn typed(&self, type_name: &str) -> Result<JsObject> {
+    let mut obj = self.env.create_object()?;
+    obj.set_named_property("type", self.env.create_string(type_name)?)?;
+    Ok(obj)
+  }
+
+  /// Put the metadata of a schema in the free-form `metadata` keyword.
+  pub fn set_metadata(&self, obj: &mut JsObject, base: &BValueBase) -> Result<()> {
+    let meta = &base.meta;
+    let mut metadata = self.env.create_object()?;
+    let mut has_metadata = false;
+    if let Some(title) = &meta.title {
+      metadata.set_named_property("title", self.env.create_string(title)?)?;
+      has_metadata = true;
+    }
+    if let Some(description) = &meta.description {
+      metadata.set_named_property("description", self.env.create_string(description)?)?;
+      has_metadata = true;
+    }
+    if let Some(deprecated) = &meta.deprecated {
+      metadata.set_named_property("deprecated", self.env.create_string(deprecated)?)?;
+      has_metadata = true;
+    }
+    if has_metadata {
+      obj.set_named_property("metadata", metadata)?;
+    }
+    Ok(())
+  }
+}
+
+/// Types of the type form, which are all numbers except for booleans and strings.
+const NUMBER_TYPES: [&str; 8] = [
+  "int8", "uint8", "int16", "uint16", "int32", "uint32", "float32", "float64",
+];
+
+/// Translate a JTD schema to a definition for `fromDefinition()`.
+pub fn definition_from_jtd(env: Env, schema: JsUnknown) -> Result<JsObject> {
+  let root = ClonableJsUnknown::new(env, schema)?;
+  let mut compiler = JtdCompiler {
+    env,
+    root,
+    refs: vec![],
+  };
+  let schema = compiler.root.create_clone()?;
+  compiler.compile(schema, true)
+}
+
+struct JtdCompiler {
+  env: Env,
+  root: ClonableJsUnknown,
+  /// Definitions being compiled, to reject recursive schemas.
+  refs: Vec<String>,
+}
+
+impl JtdCompiler {
+  fn compile(&mut self, schema: JsUnknown, root: bool) -> Result<JsObject> {
+    let env = self.env;
+    let mut reader = DefinitionReader::new(env, schema)?;
+    if root {
+      // definitions are only compiled when referenced
+      reader.get("definitions")?;
+    }
+    let nullable = reader.flag("nullable")?;
+    let metadata = reader.object("metadata")?;
+
+    let definition = self.form(&mut reader)?;
+    if let Some(keyword) = reader.unread_key()? {
+      return Err(invalid_arg(
+        format!("Unsupported keyword '{}'", keyword).as_str(),
+      ));
+    }
+    let Some(mut definition) = definition else {
+      return Err(invalid_arg(
+        "The empty form accepts any value, which is not supported",
+      ));
+    };
+
+    if nullable {
+      definition.set_named_property("nullable", env.get_boolean(true)?)?;
+    }
+    if let Some(metadata) = metadata {
+      // other metadata is free-form and has no meaning for validation
+      for key in ["title", "description", "deprecated"] {
+        let value = metadata.get_named_property::<JsUnknown>(key)?;
+        if value.get_type()? == ValueType::String {
+          definition.set_named_property(key, value)?;
+        }
+      }
+    }
+    Ok(definition)
+  }
+
+  /// Compile the form of a schema, `None` when no form keyword is present.
+  fn form(&mut self, reader: &mut DefinitionReader) -> Result<Option<JsObject>> {
+    let env = self.env;
+    if let Some(name) = reader.str("ref")? {
+      return self.reference(&name).map(Some);
+    }
+    if let Some(type_name) = reader.str("type")? {
+      return self
+        .typed(&type_name)
+        .map(Some)
+        .map_err(|err| with_path(err, "type"));
+    }
+    if let Some(values) = reader.strings("enum")? {
+      if values.is_empty() {
+        return Err(with_path(invalid_arg("No values given"), "enum"));
+      }
+      let mut definition = type_definition(env, "enum")?;
+      let mut array = env.create_array_with_length(values.len())?;
+      for (i, value) in values.iter().enumerate() {
+        array.set_element(i as u32, env.create_string(value)?)?;
+      }
+      definition.set_named_property("values", array)?;
+      return Ok(Some(definition));
+    }
+    if let Some(elements) = reader.get("elements")? {
+      let mut definition = type_definition(env, "array")?;
+      let element = self
+        .compile(elements, false)
+        .map_err(|err| with_path(err, "elements"))?;
+      definition.set_named_property("element", element)?;
+      return Ok(Some(definition));
+    }
+    if reader.has("properties")? || reader.has("optionalProperties")? {
+      return self.properties(reader).map(Some);
+    }
+    if let Some(values) = reader.get("values")? {
+      let mut definition = type_definition(env, "object")?;
+      definition.set_named_property("shape", env.create_object()?)?;
+      let catchall = self
+        .compile(values, false)
+        .map_err(|err| with_path(err, "values"))?;
+      definition.set_named_property("catchall", catchall)?;
+      return Ok(Some(definition));
+    }
+    if let Some(key) = reader.str("discriminator")? {
+      return self.discriminator(reader, &key).map(Some);
+    }
+    Ok(None)
+  }
+
+  fn reference(&mut self, name: &str) -> Result<JsObject> {
+    if self.refs.iter().any(|reference| reference == name) {
+      return Err(with_path(
+        invalid_arg(format!("Recursive ref '{}' is not supported", name).as_str()),
+        "ref",
+      ));
+    }
+    let root = self.root.create_clone()?;
+    let root = unsafe { root.cast::<JsObject>() };
+    let definitions = root.get_named_property::<JsUnknown>("definitions")?;
+    let schema = match definitions.get_type()? {
+      ValueType::Object => {
+        unsafe { definitions.cast::<JsObject>() }.get_named_property::<JsUnknown>(name)?
+      }
+      _ => definitions,
+    };
+    if schema.get_type()? != ValueType::Object {
+      return Err(with_path(
+        invalid_arg(format!("Unknown definition '{}'", name).as_str()),
+        "ref",
+      ));
+    }
+
+    self.refs.push(name.to_string());
+    let definition = self
+      .compile(schema, false)
+      .map_err(|err| with_path(with_path(err, name), "definitions"));
+    self.refs.pop();
+    definition
+  }
+
+  fn typed(&self, type_name: &str) -> Result<JsObject> {
+    let env = self.env;
+    match type_name {
+      "boolean" | "string" => type_definition(env, type_name),
+      "timestamp" => {
+        let mut definition = type_definition(env, "string")?;
+        definition.set_named_property("format", env.create_string("date-time")?)?;
+        Ok(definition)
+      }
+      _ if NUMBER_TYPES.contains(&type_name) => {
+        let mut definition = type_definition(env, "number")?;
+        definition.set_named_property("width", env.create_string(type_name)?)?;
+        Ok(definition)
+      }
+      _ => Err(invalid_arg(
+        format!("Unknown type '{}'", type_name).as_str(),
+      )),
+    }
+  }
+
+  fn properties(&mut self, reader: &mut DefinitionReader) -> Result<JsObject> {
+    let env = self.env;
+    let mut shape = env.create_object()?;
+    for (keyword, optional) in [("properties", false), ("optionalProperties", true)] {
+      let Some(properties) = reader.object(keyword)? else {
+        continue;
+      };
+      for key in JsObject::keys(&properties)? {
+        if shape.has_named_property(&key)? {
+          return Err(with_path(
+            invalid_arg(format!("Key '{}' is in properties and optionalProperties", key).as_str()),
+            keyword,
+          ));
+        }
+        let mut property = self
+          .compile(properties.get_named_property::<JsUnknown>(&key)?, false)
+          .map_err(|err| with_path(with_path(err, &key), keyword))?;
+        if optional {
+          property.set_named_property("optional", env.get_boolean(true)?)?;
+        }
+        shape.set_named_property(&key, property)?;
+      }
+    }
+
+    let mut definition = type_definition(env, "object")?;
+    definition.set_named_property("shape", shape)?;
+    // JTD rejects unknown keys unless they are allowed, allowed keys are kept
+    let key = match reader.flag("additionalProperties")? {
+      true => "passthrough",
+      false => "strict",
+    };
+    definition.set_named_property(key, env.get_boolean(true)?)?;
+    Ok(definition)
+  }
+
+  /// Tagged union, the tag is added to the shape of every option.
+  fn discriminator(&mut self, reader: &mut DefinitionReader, key: &str) -> Result<JsObject> {
+    let env = self.env;
+    let Some(mapping) = reader.object("mapping")? else {
+      return Err(invalid_arg("Discriminator without 'mapping'"));
+    };
+
+    let tags = JsObject::keys(&mapping)?;
+    if tags.is_empty() {
+      return Err(with_path(invalid_arg("No schemas given"), "mapping"));
+    }
+    let mut options = env.create_array_with_length(tags.len())?;
+    for (i, tag) in tags.iter().enumerate() {
+      let schema = mapping.get_named_property::<JsUnknown>(tag)?;
+      let option = self
+        .tagged_option(schema, key, tag)
+        .map_err(|err| with_path(with_path(err, tag), "mapping"))?;
+      options.set_element(i as u32, option)?;
+    }
+
+    let mut definition = type_definition(env, "union")?;
+    definition.set_named_property("options", options)?;
+    Ok(definition)
+  }
+
+  fn tagged_option(&mut self, schema: JsUnknown, key: &str, tag: &str) -> Result<JsObject> {
+    let env = self.env;
+    if schema.get_type()? != ValueType::Object || schema.is_array()? {
+      return Err(invalid_arg(
+        format!("expect Object, got: {}", schema.get_type()?).as_str(),
+      ));
+    }
+    let obj = unsafe { schema.cast::<JsObject>() };
+    let is_properties =
+      obj.has_named_property("properties")? || obj.has_named_property("optionalProperties")?;
+    if !is_properties {
+      return Err(invalid_arg(
+        "Mapping schemas have to be of the properties form",
+      ));
+    }
+    let nullable = obj.get_named_property::<JsUnknown>("nullable")?;
+    if nullable.get_type()? == ValueType::Boolean && nullable.coerce_to_bool()?.get_value()? {
+      return Err(invalid_arg("Mapping schemas can not be nullable"));
+    }
+
+    let definition = self.compile(obj.into_unknown(), false)?;
+    let mut shape = definition.get_named_property::<JsObject>("shape")?;
+    if shape.has_named_property(key)? {
+      return Err(invalid_arg(
+        format!(
+          "Mapping schemas can not have the discriminator '{}' as property",
+          key
+        )
+        .as_str(),
+      ));
+    }
+    let mut tag_definition = type_definition(env, "enum")?;
+    let mut values = env.create_array_with_length(1)?;
+    values.set_element(0, env.create_string(tag)?)?;
+    tag_definition.set_named_property("values", values)?;
+    shape.set_named_property(key, tag_definition)?;
+    Ok(definition)
+  }
+}
@@ -1,12 +1,27 @@
 use common::invalid_arg;
-use napi::{JsUnknown, Result, ValueType};
+use context::ParseContext;
+use effect::{apply_effects, check_effects, Effect, JsCallback};
+use error::{Error, Result};
+use napi::{Env, JsObject, JsUnknown, ValueType};
 
 mod array;
 mod boolean;
 mod common;
+mod conditional;
+mod context;
+mod definition;
+mod effect;
+mod enumeration;
+mod error;
+mod format;
+mod instance;
+mod json_schema;
+mod jtd;
+mod metadata;
 mod null;
 mod number;
 mod object;
+mod rule;
 mod string;
 mod tuple;
 mod undefined;
@@ -22,8 +37,10 @@ pub enum BType {
   Boolean(boolean::BBoolean),
   Number(number::BNumber),
   String(string::BString),
+  Enum(enumeration::BEnum),
   Array(array::BArray),
   Object(object::BObject),
+  Instance(instance::BInstance),
   // Map,
   // Set,
   // Date,
@@ -32,22 +49,21 @@ pub enum BType {
   // Arraybuffer,
   Union(union::BUnion),
   Tuple(tuple::BTuple),
+  Conditional(conditional::BConditional),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct BValueBase {
   optional: bool,
   nullable: bool,
+  effects: Vec<Effect>,
+  preprocess: Vec<JsCallback>,
+  /// Nominal type name of the output, used in errors.
+  brand: Option<String>,
+  meta: metadata::Metadata,
 }
 
 impl BValueBase {
-  pub fn default() -> BValueBase {
-    BValueBase {
-      optional: false,
-      nullable: false,
-    }
-  }
-
   pub fn skip_parse(&self, value: &JsUnknown) -> Result<bool> {
     let value_type = value.get_type()?;
 
@@ -66,6 +82,55 @@ impl BValueBase {
 
     Ok(false)
   }
+
+  /// Like `skip_parse` without building errors, returns the check result for undefined and null.
+  pub fn skip_check(&self, value: &JsUnknown) -> Result<Option<bool>> {
+    Ok(match value.get_type()? {
+      ValueType::Undefined => Some(self.optional),
+      ValueType::Null => Some(self.nullable),
+      _ => None,
+    })
+  }
+
+  /// Run preprocess functions on the raw value, the last added runs first.
+  pub fn preprocess(&self, env: Env, value: JsUnknown) -> Result<JsUnknown> {
+    self
+      .preprocess
+      .iter()
+      .rev()
+      .try_fold(value, |value, preprocess| preprocess.call(env, &[value]))
+  }
+
+  /// Run custom effects on a value that passed the native checks.
+  pub fn apply_effects(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    apply_effects(&self.effects, ctx, value)
+  }
+
+  /// Collect a warning when a value of a deprecated schema is present in the input.
+  pub fn warn_deprecated(&self, ctx: &mut ParseContext, value: &JsUnknown) -> Result<()> {
+    if let Some(warning) = self.meta.deprecation_warning() {
+      if value.get_type()? != ValueType::Undefined {
+        ctx.warn(warning);
+      }
+    }
+    Ok(())
+  }
+
+  /// Name the expected brand in validation errors, e.g. `Expected UserId: expect String, got: Number`.
+  pub fn brand_error(&self, error: Error) -> Error {
+    match &self.brand {
+      Some(brand) if error.status == napi::Status::InvalidArg => Error {
+        reason: format!("Expected {}: {}", brand, error.reason),
+        ..error
+      },
+      _ => error,
+    }
+  }
+
+  /// Check custom effects on a value that passed the native checks.
+  pub fn check_effects(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    check_effects(&self.effects, env, value)
+  }
 }
 
 #[napi]
@@ -74,60 +139,140 @@ pub struct BWrapped {
   inner: BType,
 }
 
+/// Build a schema from a definition returned by `toDefinition()`, without evaluating JS code.
+/// Schemas with effects, preprocess functions or instance checks can not be restored.
+#[napi(
+  ts_args_type = "definition: SchemaDefinition",
+  ts_return_type = "BValue"
+)]
+pub fn from_definition(env: Env, definition: JsUnknown) -> Result<JsUnknown> {
+  let b_type = definition::btype_from_definition(env, definition)
+    .map_err(|err| invalid_arg(format!("Invalid definition: {}", err).as_str()))?;
+  definition::btype_into_js(env, b_type)
+}
+
+/// Build a schema from a JSON Schema document, supporting a subset of draft-07 and 2020-12.
+/// Fails for keywords which can not be represented instead of ignoring them.
+#[napi(
+  js_name = "fromJSONSchema",
+  ts_args_type = "schema: Record<string, unknown>",
+  ts_return_type = "BValue"
+)]
+pub fn from_json_schema(env: Env, schema: JsUnknown) -> Result<JsUnknown> {
+  let invalid = |err: Error| invalid_arg(format!("Invalid JSON Schema: {}", err).as_str());
+  let definition = json_schema::definition_from_json_schema(env, schema).map_err(invalid)?;
+  let b_type =
+    definition::btype_from_definition(env, definition.into_unknown()).map_err(invalid)?;
+  definition::btype_into_js(env, b_type)
+}
+
+/// Build a schema from a JSON Type Definition (RFC 8927) schema.
+#[napi(
+  js_name = "fromJTD",
+  ts_args_type = "schema: Record<string, unknown>",
+  ts_return_type = "BValue"
+)]
+pub fn from_jtd(env: Env, schema: JsUnknown) -> Result<JsUnknown> {
+  let invalid = |err: Error| invalid_arg(format!("Invalid JTD schema: {}", err).as_str());
+  let definition = jtd::definition_from_jtd(env, schema).map_err(invalid)?;
+  let b_type =
+    definition::btype_from_definition(env, definition.into_unknown()).map_err(invalid)?;
+  definition::btype_into_js(env, b_type)
+}
+
+/// Create the `components` object of an OpenAPI 3.1 document for named schemas.
+/// Schemas used within other schemas are referenced with `$ref` to their component.
+#[napi(
+  js_name = "toOpenAPIComponents",
+  ts_args_type = "schemas: Record<string, BValue>",
+  ts_return_type = "{ schemas: Record<string, Record<string, unknown>> }"
+)]
+pub fn to_openapi_components(env: Env, schemas: JsObject) -> Result<JsObject> {
+  let mut components = vec![];
+  for name in JsObject::keys(&schemas)? {
+    let schema = schemas.get_named_property::<JsObject>(&name)?;
+    components.push((name, common::unwrap_schema(env, schema)?));
+  }
+  json_schema::to_openapi_components(env, components)
+}
+
 #[napi(object)]
 pub struct ParseSafeRes {
   pub success: bool,
   pub data: Option<JsUnknown>,
   pub reason: Option<String>,
+  /// Warnings for deprecated values present in the input.
+  pub warnings: Option<Vec<String>>,
 }
 
 #[macro_export]
-macro_rules! impl_parse_safe_method {
+macro_rules! impl_parse_methods {
   ($S:ident) => {
-    use crate::ParseSafeRes;
+    use $crate::{context::ParseContext, ParseSafeRes};
 
     #[napi]
     impl $S {
+      #[napi(ts_return_type = "R")]
+      pub fn parse(&self, env: Env, value: JsUnknown) -> $crate::error::Result<JsUnknown> {
+        self.parse_root(&mut ParseContext::new(env), value)
+      }
+
       #[napi(ts_return_type = "ParseSafe<R>")]
-      pub fn parse_safe(&self, value: JsUnknown) -> ParseSafeRes {
-        match self.parse(value) {
+      pub fn parse_safe(&self, env: Env, value: JsUnknown) -> ParseSafeRes {
+        let mut ctx = ParseContext::new(env);
+        let result = self.parse_root(&mut ctx, value);
+        let warnings = ctx.take_warnings();
+        match result {
           Ok(data) => ParseSafeRes {
             success: true,
             data: Some(data),
             reason: None,
+            warnings,
           },
           Err(error) => ParseSafeRes {
             success: false,
             data: None,
-            reason: Some(error.reason),
+            reason: Some(error.to_string()),
+            warnings,
           },
         }
       }
-    }
-  };
-}
 
-#[macro_export]
-macro_rules! impl_parse_safe_method_with_env {
-  ($S:ident) => {
-    use crate::ParseSafeRes;
+      /// Check whether a value is valid, faster than `parseSafe()` as no errors are built.
+      /// Unknown object keys are never stripped and values are not mutated.
+      #[napi(ts_args_type = "value: unknown", ts_return_type = "value is R")]
+      pub fn is(&self, env: Env, value: JsUnknown) -> $crate::error::Result<bool> {
+        Ok(self.check_with(env, value)?.is_some())
+      }
+
+      /// Parse allowing async refinements and transforms, independent async steps run concurrently.
+      #[napi(ts_return_type = "Promise<R>")]
+      pub fn parse_async(&self, env: Env, value: JsUnknown) -> Result<JsObject> {
+        let mut ctx = ParseContext::new_async(env);
+        let result = self.parse_root(&mut ctx, value);
+        ctx.settle_root(result)
+      }
+
+      #[napi(ts_return_type = "Promise<ParseSafe<R>>")]
+      pub fn parse_safe_async(&self, env: Env, value: JsUnknown) -> Result<JsObject> {
+        let mut ctx = ParseContext::new_async(env);
+        let result = self.parse_root(&mut ctx, value);
+        let warnings = ctx.warnings();
+        $crate::context::into_parse_safe(env, ctx.settle_root(result)?, warnings)
+      }
+    }
 
-    #[napi]
     impl $S {
-      #[napi(ts_return_type = "ParseSafe<R>")]
-      pub fn parse_safe(&self, env: Env, value: JsUnknown) -> ParseSafeRes {
-        match self.parse(env, value) {
-          Ok(data) => ParseSafeRes {
-            success: true,
-            data: Some(data),
-            reason: None,
-          },
-          Err(error) => ParseSafeRes {
-            success: false,
-            data: None,
-            reason: Some(error.reason),
-          },
-        }
+      /// Parse a value which is not nested in another schema.
+      fn parse_root(
+        &self,
+        ctx: &mut ParseContext,
+        value: JsUnknown,
+      ) -> $crate::error::Result<JsUnknown> {
+        self.base.warn_deprecated(ctx, &value)?;
+        self
+          .parse_with(ctx, value)
+          .map_err(|err| self.base.brand_error(err))
       }
     }
   };
@@ -135,7 +280,7 @@ macro_rules! impl_parse_safe_method_with_env {
 
 #[macro_export]
 macro_rules! impl_base_methods {
-  ($S:ident, $b_type:ident, $optional:expr, $nullable:expr, $nullish:expr, $required:expr, $non_nullable:expr) => {
+  ($S:ident, $b_type:ident, $optional:expr, $nullable:expr, $nullish:expr, $required:expr, $non_nullable:expr, $transformed:expr, $branded:expr) => {
     #[napi]
     impl $S {
       // #[napi(constructor)]
@@ -147,6 +292,10 @@ macro_rules! impl_base_methods {
         &self.base
       }
 
+      pub fn get_base_mut(&mut self) -> &mut BValueBase {
+        &mut self.base
+      }
+
       #[napi(ts_return_type = $optional)]
       pub fn optional(&self) -> Self {
         let mut c = self.clone();
@@ -183,14 +332,166 @@ macro_rules! impl_base_methods {
         c
       }
 
+      /// Brand the output type, making it incompatible with structurally identical types.
+      /// The brand name is used in errors, e.g. `Expected UserId: ...`.
+      #[napi(ts_args_type = "name: B", ts_return_type = $branded)]
+      pub fn brand(&self, name: String) -> Self {
+        let mut c = self.clone();
+        c.base.brand = Some(name);
+        c
+      }
+
+      /// Set a human readable description of the value.
+      #[napi(ts_return_type = "this")]
+      pub fn describe(&self, description: String) -> Self {
+        let mut c = self.clone();
+        c.base.meta.description = Some(description);
+        c
+      }
+
+      #[napi(ts_return_type = "this")]
+      pub fn title(&self, title: String) -> Self {
+        let mut c = self.clone();
+        c.base.meta.title = Some(title);
+        c
+      }
+
+      #[napi(ts_args_type = "examples: R[]", ts_return_type = "this")]
+      pub fn examples(&self, env: Env, examples: napi::JsObject) -> $crate::error::Result<Self> {
+        let mut c = self.clone();
+        c.base.meta.examples = Some($crate::common::PersistentValue::new(env, examples)?);
+        Ok(c)
+      }
+
+      /// Mark the value as deprecated, `parseSafe()` returns a warning when the value is present.
+      #[napi(ts_return_type = "this")]
+      pub fn deprecated(&self, reason: Option<String>) -> Self {
+        let mut c = self.clone();
+        c.base.meta.deprecated = Some(reason.unwrap_or_default());
+        c
+      }
+
+      /// Set custom metadata, replacing previously set custom metadata.
+      #[napi(ts_args_type = "meta: Record<string, unknown>", ts_return_type = "this")]
+      pub fn meta(&self, env: Env, meta: napi::JsObject) -> $crate::error::Result<Self> {
+        let mut c = self.clone();
+        c.base.meta.custom = Some($crate::common::PersistentValue::new(env, meta)?);
+        Ok(c)
+      }
+
+      /// Metadata set by `describe()`, `title()`, `examples()`, `deprecated()` and `meta()`.
+      #[napi(getter, ts_return_type = "SchemaMetadata<R>")]
+      pub fn metadata(&self, env: Env) -> Result<JsObject> {
+        self.base.meta.to_js(env)
+      }
+
+      /// Plain JSON-serializable description of the schema and all nested schemas.
+      #[napi(ts_return_type = "SchemaDefinition")]
+      pub fn to_definition(&self, env: Env) -> $crate::error::Result<napi::JsObject> {
+        self.definition(env)
+      }
+
+      /// JSON Schema document describing the values accepted by the schema.
+      /// Custom checks, effects and rules between keys are not included.
+      #[napi(js_name = "toJSONSchema", ts_return_type = "Record<string, unknown>")]
+      pub fn to_json_schema(
+        &self,
+        env: Env,
+        options: Option<$crate::json_schema::JsonSchemaOptions>,
+      ) -> $crate::error::Result<napi::JsObject> {
+        $crate::json_schema::to_json_schema(env, &$crate::BType::$b_type(self.clone()), options)
+      }
+
+      /// JSON Type Definition (RFC 8927) schema describing the values accepted by the schema.
+      /// Constraints without a JTD keyword, like lengths and ranges, are rejected.
+      /// Custom checks, effects and rules between keys are not included.
+      #[napi(js_name = "toJTD", ts_return_type = "Record<string, unknown>")]
+      pub fn to_jtd(&self, env: Env) -> $crate::error::Result<napi::JsObject> {
+        $crate::jtd::to_jtd(env, &$crate::BType::$b_type(self.clone()))
+      }
+
+      /// Brand name set by `brand()`.
+      #[napi(getter, ts_return_type = "string | null")]
+      pub fn brand_name(&self) -> Option<String> {
+        self.base.brand.clone()
+      }
+
       /// For internal use only!
       /// Create BWrapped instance of this value to be used by other methods
       #[napi(js_name = "_toWrapped")]
-      pub fn to_wrapped(&self) -> crate::BWrapped {
-        crate::BWrapped {
-          inner: crate::BType::$b_type(self.to_owned()),
+      pub fn to_wrapped(&self) -> $crate::BWrapped {
+        $crate::BWrapped {
+          inner: $crate::BType::$b_type(self.to_owned()),
         }
       }
+
+      /// Add a custom check, run after the native checks passed.
+      /// Parsing fails with `message` when `check` returns a falsy value.
+      #[napi(
+        ts_args_type = "check: (value: NonNullable<R>) => unknown, message?: string",
+        ts_return_type = "this"
+      )]
+      pub fn refine(
+        &self,
+        env: Env,
+        check: napi::JsFunction,
+        message: Option<String>,
+      ) -> $crate::error::Result<Self> {
+        let mut c = self.clone();
+        c.base.effects.push($crate::effect::Effect::Refine {
+          check: $crate::effect::JsCallback::new(env, check)?,
+          message: message.unwrap_or_else(|| "Value did not pass refinement".to_string()),
+        });
+        Ok(c)
+      }
+
+      /// Add a custom check able to report issues on sub paths of the value using `ctx.addIssue()`.
+      #[napi(
+        ts_args_type = "refinement: (value: NonNullable<R>, ctx: RefinementContext) => void",
+        ts_return_type = "this"
+      )]
+      pub fn super_refine(&self, env: Env, refinement: napi::JsFunction) -> $crate::error::Result<Self> {
+        let mut c = self.clone();
+        c.base.effects.push($crate::effect::Effect::SuperRefine(
+          $crate::effect::JsCallback::new(env, refinement)?,
+        ));
+        Ok(c)
+      }
+
+      /// Map the value to a new value after the native checks and previous effects passed.
+      #[napi(ts_args_type = "transform: (value: NonNullable<R>) => T", ts_return_type = $transformed)]
+      pub fn transform(&self, env: Env, transform: napi::JsFunction) -> $crate::error::Result<Self> {
+        let mut c = self.clone();
+        c.base.effects.push($crate::effect::Effect::Transform(
+          $crate::effect::JsCallback::new(env, transform)?,
+        ));
+        Ok(c)
+      }
+
+      /// For internal use only!
+      /// Run a function on the raw value before parsing, use `preprocess()` instead.
+      #[napi(
+        js_name = "_preprocess",
+        ts_args_type = "preprocess: (value: unknown) => unknown",
+        ts_return_type = "this"
+      )]
+      pub fn add_preprocess(&self, env: Env, preprocess: napi::JsFunction) -> $crate::error::Result<Self> {
+        let mut c = self.clone();
+        c.base
+          .preprocess
+          .push($crate::effect::JsCallback::new(env, preprocess)?);
+        Ok(c)
+      }
+
+      /// Parse the output of this schema with another schema.
+      #[napi(ts_args_type = "schema: BValue<T>", ts_return_type = $transformed)]
+      pub fn pipe(&self, env: Env, schema: napi::JsObject) -> $crate::error::Result<Self> {
+        let mut c = self.clone();
+        c.base.effects.push($crate::effect::Effect::Pipe(Box::new(
+          $crate::common::unwrap_schema(env, schema)?,
+        )));
+        Ok(c)
+      }
     }
   };
 }
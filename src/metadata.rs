@@ -0,0 +1,50 @@
+use napi::{Env, JsObject};
+
+use crate::{common::PersistentValue, error::Result};
+
+/// Descriptive information about a schema, not used for validation.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Metadata {
+  pub title: Option<String>,
+  pub description: Option<String>,
+  /// Array of example values.
+  pub examples: Option<PersistentValue>,
+  /// Reason the value is deprecated, empty when no reason was given.
+  pub deprecated: Option<String>,
+  /// Object with custom metadata.
+  pub custom: Option<PersistentValue>,
+}
+
+impl Metadata {
+  /// Create the object returned by the `metadata` getter of schemas.
+  pub fn to_js(&self, env: Env) -> Result<JsObject> {
+    let mut obj = env.create_object()?;
+    if let Some(title) = &self.title {
+      obj.set_named_property("title", env.create_string(title)?)?;
+    }
+    if let Some(description) = &self.description {
+      obj.set_named_property("description", env.create_string(description)?)?;
+    }
+    if let Some(examples) = &self.examples {
+      obj.set_named_property("examples", examples.get::<JsObject>(env)?)?;
+    }
+    if let Some(deprecated) = &self.deprecated {
+      obj.set_named_property("deprecated", env.create_string(deprecated)?)?;
+    }
+    if let Some(custom) = &self.custom {
+      obj.set_named_property("meta", custom.get::<JsObject>(env)?)?;
+    }
+    Ok(obj)
+  }
+
+  /// Warning emitted when a value of a deprecated schema is present in the input.
+  pub fn deprecation_warning(&self) -> Option<String> {
+    self
+      .deprecated
+      .as_ref()
+      .map(|reason| match reason.is_empty() {
+        true => "Value is deprecated".to_string(),
+        false => format!("Value is deprecated: {}", reason),
+      })
+  }
+}
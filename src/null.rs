@@ -1,6 +1,9 @@
-use napi::{JsNull, JsUnknown, Result};
+use napi::{Env, JsNull, JsObject, JsUnknown, ValueType};
 
-use crate::{impl_base_methods, impl_parse_safe_method, BValueBase};
+use crate::{
+  definition::Definition, error::Result, impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter, BValueBase,
+};
 
 #[napi]
 #[derive(Debug, Clone, PartialEq)]
@@ -15,9 +18,11 @@ impl_base_methods!(
   "BNull<R | null>",
   "BNull<R | null | undefined>",
   "BNull<Exclude<R, undefined>>",
-  "BNull<Exclude<R, null>>"
+  "BNull<Exclude<R, null>>",
+  "BNull<T | Extract<R, null | undefined>>",
+  "BNull<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method!(BNull);
+impl_parse_methods!(BNull);
 
 #[napi]
 impl BNull {
@@ -25,19 +30,44 @@ impl BNull {
   pub fn default() -> BNull {
     BNull {
       base: BValueBase {
-        optional: false,
         nullable: true,
+        ..BValueBase::default()
       },
     }
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, value: JsUnknown) -> Result<JsUnknown> {
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let definition = Definition::new(env, "null", &self.base)?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(base: BValueBase) -> Self {
+    BNull { base }
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    writer.typed("null")
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
 
     let js_undefined: JsNull = value.try_into()?;
-    Ok(js_undefined.into_unknown())
+    self.base.apply_effects(ctx, js_undefined.into_unknown())
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    match value.get_type()? {
+      ValueType::Null => self.base.check_effects(env, value),
+      _ => Ok(None),
+    }
   }
 }
@@ -1,6 +1,14 @@
-use napi::{JsNumber, JsUnknown, Result};
+use napi::{Env, JsNumber, JsObject, JsUnknown, ValueType};
 
-use crate::{common::invalid_arg, impl_base_methods, impl_parse_safe_method, BValueBase};
+use crate::{
+  common::{invalid_arg, with_path},
+  definition::{check_range, Definition, DefinitionReader},
+  error::Result,
+  impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter,
+  jtd::JtdWriter,
+  BValueBase,
+};
 
 #[napi]
 #[derive(Debug, Clone, PartialEq)]
@@ -8,6 +16,71 @@ pub struct BNumber {
   base: BValueBase,
   min: Option<f64>,
   max: Option<f64>,
+  int: bool,
+  width: Option<NumberWidth>,
+}
+
+/// Numeric types of JSON Type Definition, restricting the range of numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberWidth {
+  Int8,
+  Uint8,
+  Int16,
+  Uint16,
+  Int32,
+  Uint32,
+  Float32,
+  Float64,
+}
+
+const WIDTHS: [(&str, NumberWidth); 8] = [
+  ("int8", NumberWidth::Int8),
+  ("uint8", NumberWidth::Uint8),
+  ("int16", NumberWidth::Int16),
+  ("uint16", NumberWidth::Uint16),
+  ("int32", NumberWidth::Int32),
+  ("uint32", NumberWidth::Uint32),
+  ("float32", NumberWidth::Float32),
+  ("float64", NumberWidth::Float64),
+];
+
+impl NumberWidth {
+  pub fn from_name(name: &str) -> Result<Self> {
+    WIDTHS
+      .iter()
+      .find(|(width_name, _)| *width_name == name)
+      .map(|(_, width)| *width)
+      .ok_or_else(|| invalid_arg(format!("Unknown number width '{}'", name).as_str()))
+  }
+
+  pub fn name(&self) -> &'static str {
+    WIDTHS
+      .iter()
+      .find(|(_, width)| width == self)
+      .map(|(name, _)| *name)
+      .unwrap_or_default()
+  }
+
+  /// Range of integer widths, floats only have to be finite.
+  fn int_range(&self) -> Option<(f64, f64)> {
+    match self {
+      NumberWidth::Int8 => Some((i8::MIN.into(), i8::MAX.into())),
+      NumberWidth::Uint8 => Some((u8::MIN.into(), u8::MAX.into())),
+      NumberWidth::Int16 => Some((i16::MIN.into(), i16::MAX.into())),
+      NumberWidth::Uint16 => Some((u16::MIN.into(), u16::MAX.into())),
+      NumberWidth::Int32 => Some((i32::MIN.into(), i32::MAX.into())),
+      NumberWidth::Uint32 => Some((u32::MIN.into(), u32::MAX.into())),
+      NumberWidth::Float32 | NumberWidth::Float64 => None,
+    }
+  }
+
+  fn is_valid(&self, n: f64) -> bool {
+    match (self, self.int_range()) {
+      (_, Some((min, max))) => n.fract() == 0. && n >= min && n <= max,
+      (NumberWidth::Float32, None) => n.is_finite() && n.abs() <= f32::MAX.into(),
+      (_, None) => n.is_finite(),
+    }
+  }
 }
 
 impl_base_methods!(
@@ -17,9 +90,11 @@ impl_base_methods!(
   "BNumber<R | null>",
   "BNumber<R | null | undefined>",
   "BNumber<Exclude<R, undefined>>",
-  "BNumber<Exclude<R, null>>"
+  "BNumber<Exclude<R, null>>",
+  "BNumber<T | Extract<R, null | undefined>>",
+  "BNumber<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method!(BNumber);
+impl_parse_methods!(BNumber);
 
 #[napi]
 impl BNumber {
@@ -29,11 +104,67 @@ impl BNumber {
       base: BValueBase::default(),
       min: None,
       max: None,
+      int: false,
+      width: None,
     }
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, value: JsUnknown) -> Result<JsUnknown> {
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "number", &self.base)?;
+    definition.set_f64("min", self.min)?;
+    definition.set_f64("max", self.max)?;
+    definition.set_flag("int", self.int)?;
+    definition.set_str("width", self.width.map(|width| width.name()))?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    let (min, max) = (reader.f64("min")?, reader.f64("max")?);
+    check_range("min", min, "max", max)?;
+    Ok(BNumber {
+      base,
+      min,
+      max,
+      int: reader.flag("int")?,
+      width: reader
+        .str("width")?
+        .map(|width| NumberWidth::from_name(&width).map_err(|err| with_path(err, "width")))
+        .transpose()?,
+    })
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let range = self.width.and_then(|width| width.int_range());
+    let int = self.int || range.is_some();
+    let mut obj = writer.typed(if int { "integer" } else { "number" })?;
+    // the tighter bound of the range and the width
+    let (min, max) = match range {
+      Some((width_min, width_max)) => (
+        Some(self.min.map_or(width_min, |min| min.max(width_min))),
+        Some(self.max.map_or(width_max, |max| max.min(width_max))),
+      ),
+      None => (self.min, self.max),
+    };
+    writer.set_f64(&mut obj, "minimum", min)?;
+    writer.set_f64(&mut obj, "maximum", max)?;
+    Ok(obj)
+  }
+
+  pub fn jtd(&self, writer: &mut JtdWriter) -> Result<JsObject> {
+    if self.min.is_some() || self.max.is_some() {
+      return Err(invalid_arg("Number ranges can not be represented in JTD"));
+    }
+    let width = self.width.unwrap_or(NumberWidth::Float64);
+    if self.int && width.int_range().is_none() {
+      return Err(invalid_arg(
+        "Integers can only be represented in JTD with an integer width",
+      ));
+    }
+    writer.typed(width.name())
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
@@ -41,6 +172,16 @@ impl BNumber {
     let js_number: JsNumber = value.try_into()?;
     let n = js_number.get_double()?;
 
+    if self.int && n.fract() != 0. {
+      return Err(invalid_arg("Number is not an integer"));
+    }
+    if let Some(width) = self.width {
+      if !width.is_valid(n) {
+        return Err(invalid_arg(
+          format!("Number is not a valid {}", width.name()).as_str(),
+        ));
+      }
+    }
     if let Some(min) = self.min {
       if n < min {
         return Err(invalid_arg("Number is lower than minimum"));
@@ -52,7 +193,30 @@ impl BNumber {
       }
     }
 
-    Ok(js_number.into_unknown())
+    self.base.apply_effects(ctx, js_number.into_unknown())
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    if value.get_type()? != ValueType::Number {
+      return Ok(None);
+    }
+    let js_number = unsafe { value.cast::<JsNumber>() };
+    let n = js_number.get_double()?;
+
+    let valid = (!self.int || n.fract() == 0.)
+      && self.width.is_none_or(|width| width.is_valid(n))
+      && self.min.is_none_or(|min| n >= min)
+      && self.max.is_none_or(|max| n <= max);
+    if !valid {
+      return Ok(None);
+    }
+
+    self.base.check_effects(env, js_number.into_unknown())
   }
 
   #[napi(ts_return_type = "BNumber<R>")]
@@ -71,6 +235,26 @@ impl BNumber {
     }
   }
 
+  #[napi(ts_return_type = "BNumber<R>")]
+  pub fn int(&self) -> Self {
+    BNumber {
+      int: true,
+      ..self.clone()
+    }
+  }
+
+  /// Restrict the number to a numeric type of JSON Type Definition, e.g. integers of `'uint8'`.
+  #[napi(
+    ts_args_type = "width: 'int8' | 'uint8' | 'int16' | 'uint16' | 'int32' | 'uint32' | 'float32' | 'float64'",
+    ts_return_type = "BNumber<R>"
+  )]
+  pub fn width(&self, width: String) -> Result<Self> {
+    Ok(BNumber {
+      width: Some(NumberWidth::from_name(&width)?),
+      ..self.clone()
+    })
+  }
+
   #[napi(ts_return_type = "BNumber<R>")]
   pub fn positive(&self) -> Self {
     BNumber {
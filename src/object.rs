@@ -1,10 +1,20 @@
 use std::collections::HashMap;
 
-use napi::{bindgen_prelude::ClassInstance, Env, JsObject, JsUnknown, Result};
+use napi::{bindgen_prelude::ClassInstance, Env, JsFunction, JsObject, JsUnknown, ValueType};
 
 use crate::{
-  common::{get_btype_base, invalid_arg, parse_btype},
-  impl_base_methods, impl_parse_safe_method_with_env, BType, BValueBase, BWrapped,
+  common::{
+    check_btype, get_btype_base, get_btype_base_mut, invalid_arg, parse_btype, transforms_value,
+    unwrap_schema, with_path, Freeze, Pattern,
+  },
+  definition::{btype_definition, check_range, missing, Definition, DefinitionReader},
+  enumeration::BEnum,
+  error::{Error, Result},
+  impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter,
+  jtd::JtdWriter,
+  rule::ObjectRule,
+  BType, BValueBase, BWrapped,
 };
 
 #[napi]
@@ -13,6 +23,46 @@ pub struct BObject {
   base: BValueBase,
   inner: HashMap<String, BType>,
   strict: bool,
+  /// Keep unknown keys without validating their values.
+  passthrough: bool,
+  /// Schema for unknown keys, which are kept instead of removed.
+  catchall: Option<Box<BType>>,
+  /// Schemas for unknown keys matching a pattern, which are kept instead of removed.
+  pattern_properties: Vec<(Pattern, BType)>,
+  /// Schema every key of the parsed object has to match.
+  property_names: Option<Box<BType>>,
+  min_properties: Option<u32>,
+  max_properties: Option<u32>,
+  /// Only allow objects with `Object.prototype` or `null` as prototype.
+  plain: bool,
+  reject_arrays: bool,
+  /// Input key for a key of the shape, maps the alias to the canonical key.
+  aliases: HashMap<String, String>,
+  /// Case of input keys which are converted to camelCase.
+  key_case: Option<KeyCase>,
+  rules: Vec<ObjectRule>,
+  readonly: Option<Freeze>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeyCase {
+  Snake,
+  Kebab,
+}
+
+impl KeyCase {
+  fn separator(&self) -> char {
+    match self {
+      KeyCase::Snake => '_',
+      KeyCase::Kebab => '-',
+    }
+  }
+}
+
+#[napi(object)]
+pub struct KeyOptions {
+  /// Key in the input value, renamed to the key of the shape when parsing.
+  pub from: Option<String>,
 }
 
 impl_base_methods!(
@@ -22,9 +72,11 @@ impl_base_methods!(
   "BObject<R | null>",
   "BObject<R | null | undefined>",
   "BObject<Exclude<R, undefined>>",
-  "BObject<Exclude<R, null>>"
+  "BObject<Exclude<R, null>>",
+  "BObject<T | Extract<R, null | undefined>>",
+  "BObject<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method_with_env!(BObject);
+impl_parse_methods!(BObject);
 
 #[napi]
 impl BObject {
@@ -38,54 +90,620 @@ impl BObject {
         .map(|(k, v)| (k.clone(), v.inner.clone()))
         .collect(),
       strict: false,
+      passthrough: false,
+      catchall: None,
+      pattern_properties: vec![],
+      property_names: None,
+      min_properties: None,
+      max_properties: None,
+      plain: false,
+      reject_arrays: false,
+      aliases: HashMap::new(),
+      key_case: None,
+      rules: vec![],
+      readonly: None,
+    }
+  }
+
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "object", &self.base)?;
+    definition.set_schema_map("shape", self.inner.iter())?;
+    definition.set_flag("strict", self.strict)?;
+    definition.set_flag("passthrough", self.passthrough)?;
+    definition.set_schema("catchall", self.catchall.as_deref())?;
+    if !self.pattern_properties.is_empty() {
+      let mut obj = env.create_object()?;
+      for (pattern, b_type) in &self.pattern_properties {
+        obj.set_named_property(pattern.as_str(), btype_definition(env, b_type)?)?;
+      }
+      definition.set("patternProperties", obj)?;
+    }
+    definition.set_schema("propertyNames", self.property_names.as_deref())?;
+    definition.set_u32("minProperties", self.min_properties)?;
+    definition.set_u32("maxProperties", self.max_properties)?;
+    definition.set_flag("plain", self.plain)?;
+    definition.set_flag("rejectArrays", self.reject_arrays)?;
+    if !self.aliases.is_empty() {
+      let mut aliases: Vec<_> = self.aliases.iter().collect();
+      aliases.sort();
+      let mut obj = env.create_object()?;
+      for (alias, key) in aliases {
+        obj.set_named_property(alias, env.create_string(key)?)?;
+      }
+      definition.set("aliases", obj)?;
+    }
+    let key_case = self.key_case.map(|key_case| match key_case {
+      KeyCase::Snake => "snake_case",
+      KeyCase::Kebab => "kebab-case",
+    });
+    definition.set_str("keyCase", key_case)?;
+    if !self.rules.is_empty() {
+      let mut rules = env.create_array_with_length(self.rules.len())?;
+      for (i, rule) in self.rules.iter().enumerate() {
+        rules.set_element(i as u32, rule.definition(env)?)?;
+      }
+      definition.set("rules", rules)?;
+    }
+    definition.set_readonly(self.readonly)?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    let inner: HashMap<String, BType> = reader
+      .schema_map("shape")?
+      .ok_or_else(|| missing("shape"))?
+      .into_iter()
+      .collect();
+
+    let pattern_properties = reader
+      .schema_map("patternProperties")?
+      .unwrap_or_default()
+      .into_iter()
+      .map(|(pattern, b_type)| {
+        let pattern = Pattern::new(&pattern).map_err(|err| with_path(err, "patternProperties"))?;
+        Ok((pattern, b_type))
+      })
+      .collect::<Result<_>>()?;
+
+    let (min_properties, max_properties) =
+      (reader.u32("minProperties")?, reader.u32("maxProperties")?);
+    check_range(
+      "minProperties",
+      min_properties,
+      "maxProperties",
+      max_properties,
+    )?;
+
+    let aliases: HashMap<String, String> = reader
+      .string_map("aliases")?
+      .unwrap_or_default()
+      .into_iter()
+      .collect();
+    if let Some((alias, key)) = aliases.iter().find(|(_, key)| !inner.contains_key(*key)) {
+      return Err(with_path(
+        invalid_arg(format!("Alias '{}' maps to unknown key '{}'", alias, key).as_str()),
+        "aliases",
+      ));
+    }
+
+    let key_case = reader
+      .choice("keyCase", &["snake_case", "kebab-case"])?
+      .map(|key_case| match key_case {
+        "snake_case" => KeyCase::Snake,
+        _ => KeyCase::Kebab,
+      });
+
+    let env = reader.env();
+    let rules = reader
+      .array("rules")?
+      .unwrap_or_default()
+      .into_iter()
+      .enumerate()
+      .map(|(i, rule)| {
+        let read = |rule| {
+          let mut rule_reader = DefinitionReader::new(env, rule)?;
+          let rule = ObjectRule::from_definition(&mut rule_reader)?;
+          rule_reader.finish()?;
+          Ok(rule)
+        };
+        read(rule).map_err(|err| with_path(err, &i.to_string()))
+      })
+      .collect::<Result<Vec<_>>>();
+    let rules = rules.map_err(|err| with_path(err, "rules"))?;
+    let (strict, passthrough) = (reader.flag("strict")?, reader.flag("passthrough")?);
+    if strict && passthrough {
+      return Err(invalid_arg(
+        "'strict' and 'passthrough' can not be combined",
+      ));
+    }
+
+    Ok(BObject {
+      base,
+      inner,
+      strict,
+      passthrough,
+      catchall: reader.schema("catchall")?.map(Box::new),
+      pattern_properties,
+      property_names: reader.schema("propertyNames")?.map(Box::new),
+      min_properties,
+      max_properties,
+      plain: reader.flag("plain")?,
+      reject_arrays: reader.flag("rejectArrays")?,
+      aliases,
+      key_case,
+      rules,
+      readonly: reader.readonly()?,
+    })
+  }
+
+  /// Required keys with an enum schema and its values, which can tag the object in a union.
+  pub fn tags(&self) -> Vec<(&String, Vec<String>)> {
+    let mut tags: Vec<_> = self
+      .inner
+      .iter()
+      .filter_map(|(key, b_type)| match b_type {
+        BType::Enum(s) if !get_btype_base(b_type).optional && !get_btype_base(b_type).nullable => {
+          Some((key, s.options()))
+        }
+        _ => None,
+      })
+      .collect();
+    tags.sort_by_key(|(key, _)| *key);
+    tags
+  }
+
+  pub fn jtd(&self, writer: &mut JtdWriter) -> Result<JsObject> {
+    if self.inner.is_empty() {
+      if let Some(catchall) = &self.catchall {
+        self.check_jtd_keys()?;
+        let mut obj = writer.env().create_object()?;
+        let values = writer
+          .schema(catchall)
+          .map_err(|err| with_path(err, "values"))?;
+        obj.set_named_property("values", values)?;
+        return Ok(obj);
+      }
+    }
+    self.jtd_properties(writer, None)
+  }
+
+  /// Write the properties form of JTD, without the `discriminator` key of a tagged union.
+  pub fn jtd_properties(
+    &self,
+    writer: &mut JtdWriter,
+    discriminator: Option<&str>,
+  ) -> Result<JsObject> {
+    if !self.aliases.is_empty() || self.key_case.is_some() {
+      return Err(invalid_arg(
+        "Objects with aliases or key case conversion can not be represented in JTD",
+      ));
+    }
+    if self.catchall.is_some() {
+      return Err(invalid_arg(
+        "Catchall schemas of objects with properties can not be represented in JTD",
+      ));
+    }
+    self.check_jtd_keys()?;
+
+    let properties = self
+      .inner
+      .iter()
+      .filter(|(key, _)| Some(key.as_str()) != discriminator);
+    let (optional, required): (Vec<_>, Vec<_>) =
+      properties.partition(|(_, b_type)| get_btype_base(b_type).optional);
+
+    let mut obj = writer.env().create_object()?;
+    let required = writer
+      .schema_map(required.into_iter())
+      .map_err(|err| with_path(err, "properties"))?;
+    obj.set_named_property("properties", required)?;
+    if !optional.is_empty() {
+      let optional = writer
+        .schema_map(optional.into_iter())
+        .map_err(|err| with_path(err, "optionalProperties"))?;
+      obj.set_named_property("optionalProperties", optional)?;
+    }
+    // unknown keys are only rejected by strict objects
+    if !self.strict {
+      obj.set_named_property("additionalProperties", writer.env().get_boolean(true)?)?;
+    }
+    Ok(obj)
+  }
+
+  /// Keys are described as in the parsed value, aliases and key case conversion are not included.
+  /// Fail for constraints on the keys, which JTD has no keywords for.
+  fn check_jtd_keys(&self) -> Result<()> {
+    if !self.pattern_properties.is_empty() {
+      return Err(invalid_arg(
+        "Pattern properties can not be represented in JTD",
+      ));
+    }
+    if self.property_names.is_some() {
+      return Err(invalid_arg(
+        "Property name schemas can not be represented in JTD",
+      ));
+    }
+    if self.min_properties.is_some() || self.max_properties.is_some() {
+      return Err(invalid_arg("Property counts can not be represented in JTD"));
+    }
+    Ok(())
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let env = writer.env();
+    let mut obj = writer.typed("object")?;
+    let properties = writer
+      .schema_map(self.inner.iter())
+      .map_err(|err| with_path(err, "properties"))?;
+    obj.set_named_property("properties", properties)?;
+
+    let mut required: Vec<_> = self
+      .inner
+      .iter()
+      .filter(|(_, b_type)| !get_btype_base(b_type).optional)
+      .map(|(key, _)| key)
+      .collect();
+    required.sort();
+    let mut required_array = env.create_array_with_length(required.len())?;
+    for (i, key) in required.into_iter().enumerate() {
+      required_array.set_element(i as u32, env.create_string(key)?)?;
+    }
+    obj.set_named_property("required", required_array)?;
+
+    if !self.pattern_properties.is_empty() {
+      let mut patterns = env.create_object()?;
+      for (pattern, b_type) in &self.pattern_properties {
+        let schema = writer
+          .schema(b_type)
+          .map_err(|err| with_path(err, "patternProperties"))?;
+        patterns.set_named_property(pattern.as_str(), schema)?;
+      }
+      obj.set_named_property("patternProperties", patterns)?;
     }
+    if let Some(catchall) = &self.catchall {
+      let schema = writer
+        .schema(catchall)
+        .map_err(|err| with_path(err, "additionalProperties"))?;
+      obj.set_named_property("additionalProperties", schema)?;
+    } else if self.strict {
+      obj.set_named_property("additionalProperties", env.get_boolean(false)?)?;
+    }
+    if let Some(property_names) = &self.property_names {
+      let schema = writer
+        .schema(property_names)
+        .map_err(|err| with_path(err, "propertyNames"))?;
+      obj.set_named_property("propertyNames", schema)?;
+    }
+    writer.set_u32(&mut obj, "minProperties", self.min_properties)?;
+    writer.set_u32(&mut obj, "maxProperties", self.max_properties)?;
+    Ok(obj)
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, env: Env, value: JsUnknown) -> Result<JsUnknown> {
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
+    let pending = ctx.pending_len();
 
-    let mut obj: JsObject = value.try_into()?;
-    let mut obj_keys = JsObject::keys(&obj)?;
+    let obj: JsObject = value.try_into()?;
+    if self.reject_arrays && obj.is_array()? {
+      return Err(invalid_arg("Expected an object, got an Array"));
+    }
+    if self.plain && !is_plain_object(ctx.env, &obj)? {
+      return Err(invalid_arg("Expected a plain object"));
+    }
+
+    let mut obj = ctx.writable(obj)?;
+    // canonical keys with their key in the input, which are renamed once the value is valid
+    let mut obj_keys = match self.renames_keys() {
+      true => self.resolve_keys(JsObject::keys(&obj)?)?,
+      false => JsObject::keys(&obj)?
+        .into_iter()
+        .map(|key| (key.clone(), key))
+        .collect(),
+    };
+    let mut property_count = obj_keys.len() as u32;
+    let mut renamed = vec![];
 
     for (key, b_type) in &self.inner {
-      let position = obj_keys.iter().position(|r| r == key);
-      if position.is_none() {
-        if get_btype_base(&b_type).optional {
+      let source = if let Some(position) = obj_keys.iter().position(|(k, _)| k == key) {
+        let (_, source) = obj_keys.remove(position);
+        self.parse_property_name(ctx, key)?;
+        source
+      } else {
+        let base = get_btype_base(b_type);
+        if base.optional {
           continue;
-        } else {
+        } else if base.preprocess.is_empty() {
           return Err(invalid_arg(
             format!("Object missing required key '{}'", &key).as_str(),
           ));
         }
+        key.clone()
+      };
+
+      // missing keys with preprocess functions are parsed as undefined
+      parse_property(ctx, &mut obj, key, &source, b_type)?;
+      if *key != source {
+        renamed.push((key.clone(), source));
+      }
+    }
+
+    // obj has unknown keys, keep keys matching a pattern or the catchall,
+    // otherwise error if strict or remove keys
+    let mut unknown_keys = vec![];
+    for (key, source) in obj_keys {
+      let mut matched = false;
+      for (pattern, b_type) in &self.pattern_properties {
+        if pattern.is_match(&key) {
+          matched = true;
+          parse_property(ctx, &mut obj, &key, &source, b_type)?;
+        }
       }
 
-      obj_keys.remove(position.unwrap());
+      if !matched {
+        match &self.catchall {
+          Some(catchall) => parse_property(ctx, &mut obj, &key, &source, catchall)?,
+          None if self.passthrough => {}
+          None => {
+            unknown_keys.push((key, source));
+            continue;
+          }
+        }
+      }
 
-      let property = obj.get_named_property_unchecked::<JsUnknown>(&key)?;
-      parse_btype(&b_type, property, env)?;
+      self.parse_property_name(ctx, &key)?;
+      if key != source {
+        renamed.push((key, source));
+      }
     }
 
-    // obj has unknown keys, error if strict, otherwise remove keys
-    if obj_keys.len() != 0 {
+    if !unknown_keys.is_empty() {
       if self.strict {
+        let keys: Vec<&str> = unknown_keys.iter().map(|(key, _)| key.as_str()).collect();
+        return Err(invalid_arg(
+          format!("Unknown key(s) [{}] in strict object", keys.join(", ")).as_str(),
+        ));
+      }
+
+      for (_, source) in &unknown_keys {
+        obj.delete_named_property(source)?;
+      }
+      property_count -= unknown_keys.len() as u32;
+    }
+
+    if let Some(min) = self.min_properties {
+      if property_count < min {
+        return Err(invalid_arg("Object has fewer properties than minimum"));
+      }
+    }
+    if let Some(max) = self.max_properties {
+      if property_count > max {
+        return Err(invalid_arg("Object has more properties than maximum"));
+      }
+    }
+
+    if !self.rules.is_empty() {
+      let get = |key: &str| {
+        let source = renamed.iter().find(|(k, _)| k == key);
+        Ok(obj.get_named_property_unchecked::<JsUnknown>(source.map_or(key, |(_, s)| s))?)
+      };
+      for rule in &self.rules {
+        if !rule.check(ctx.env, &get)? {
+          return Err(rule.to_error(&get)?);
+        }
+      }
+    }
+
+    // read all values before moving any, a source key can be the canonical key of another
+    let properties = renamed
+      .iter()
+      .map(|(_, source)| obj.get_named_property_unchecked::<JsUnknown>(source))
+      .collect::<napi::Result<Vec<_>>>()?;
+    for (_, source) in &renamed {
+      obj.delete_named_property(source)?;
+    }
+    for ((key, _), property) in renamed.iter().zip(properties) {
+      obj.set_named_property(key, property)?;
+    }
+
+    let parsed = self.base.apply_effects(ctx, obj.into_unknown())?;
+    match self.readonly {
+      Some(freeze) => freeze.apply(ctx, pending, parsed),
+      None => Ok(parsed),
+    }
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    if value.get_type()? != ValueType::Object {
+      return Ok(None);
+    }
+    let obj = unsafe { value.cast::<JsObject>() };
+    if (self.reject_arrays && obj.is_array()?) || (self.plain && !is_plain_object(env, &obj)?) {
+      return Ok(None);
+    }
+
+    // canonical keys with their key in the input, only when keys are renamed
+    let renamed = match self.renames_keys() {
+      true => match self.resolve_keys(JsObject::keys(&obj)?) {
+        Ok(keys) => Some(keys),
+        Err(_) => return Ok(None),
+      },
+      false => None,
+    };
+    let source_key = |key: &str| -> Result<Option<String>> {
+      Ok(match &renamed {
+        Some(keys) => keys.iter().find(|(k, _)| k == key).map(|(_, s)| s.clone()),
+        None => obj.has_own_property(key)?.then(|| key.to_string()),
+      })
+    };
+
+    let mut known_keys = 0;
+    for (key, b_type) in &self.inner {
+      let source = source_key(key)?;
+      if source.is_some() {
+        known_keys += 1;
+        if !self.check_property_name(env, key)? {
+          return Ok(None);
+        }
+      } else {
+        let base = get_btype_base(b_type);
+        if base.optional {
+          continue;
+        } else if base.preprocess.is_empty() {
+          return Ok(None);
+        }
+      }
+
+      let property =
+        obj.get_named_property_unchecked::<JsUnknown>(source.as_deref().unwrap_or(key))?;
+      if check_btype(b_type, property, env)?.is_none() {
+        return Ok(None);
+      }
+    }
+
+    let mut property_count = known_keys;
+    if self.scans_unknown_keys() {
+      let keys = match &renamed {
+        Some(keys) => keys.clone(),
+        None => JsObject::keys(&obj)?
+          .into_iter()
+          .map(|k| (k.clone(), k))
+          .collect(),
+      };
+
+      // unknown keys are never removed, only counted when they would be kept
+      for (key, source) in keys {
+        if self.inner.contains_key(&key) {
+          continue;
+        }
+
+        let mut matched = false;
+        for (pattern, b_type) in &self.pattern_properties {
+          if pattern.is_match(&key) {
+            matched = true;
+            let property = obj.get_named_property_unchecked::<JsUnknown>(&source)?;
+            if check_btype(b_type, property, env)?.is_none() {
+              return Ok(None);
+            }
+          }
+        }
+
+        if !matched {
+          match &self.catchall {
+            Some(catchall) => {
+              let property = obj.get_named_property_unchecked::<JsUnknown>(&source)?;
+              if check_btype(catchall, property, env)?.is_none() {
+                return Ok(None);
+              }
+            }
+            None if self.passthrough => {}
+            None if self.strict => return Ok(None),
+            None => continue,
+          }
+        }
+
+        if !self.check_property_name(env, &key)? {
+          return Ok(None);
+        }
+        property_count += 1;
+      }
+    }
+
+    let valid = self.min_properties.is_none_or(|min| property_count >= min)
+      && self.max_properties.is_none_or(|max| property_count <= max);
+    if !valid {
+      return Ok(None);
+    }
+
+    let get = |key: &str| match source_key(key)? {
+      Some(source) => Ok(obj.get_named_property_unchecked::<JsUnknown>(&source)?),
+      None => Ok(env.get_undefined()?.into_unknown()),
+    };
+    for rule in &self.rules {
+      if !rule.check(env, &get)? {
+        return Ok(None);
+      }
+    }
+
+    self.base.check_effects(env, obj.into_unknown())
+  }
+
+  fn parse_property_name(&self, ctx: &mut ParseContext, key: &str) -> Result<()> {
+    if let Some(property_names) = &self.property_names {
+      let name = ctx.env.create_string(key)?.into_unknown();
+      parse_btype(property_names, name, ctx).map_err(|err| {
+        let error = invalid_arg(&format!("Invalid key: {}", err.reason));
+        with_path(
+          Error {
+            path: err.path,
+            ..error
+          },
+          key,
+        )
+      })?;
+    }
+    Ok(())
+  }
+
+  fn check_property_name(&self, env: Env, key: &str) -> Result<bool> {
+    match &self.property_names {
+      Some(property_names) => {
+        let name = env.create_string(key)?.into_unknown();
+        Ok(check_btype(property_names, name, env)?.is_some())
+      }
+      None => Ok(true),
+    }
+  }
+
+  fn renames_keys(&self) -> bool {
+    !self.aliases.is_empty() || self.key_case.is_some()
+  }
+
+  /// Canonical key for a key of the input value.
+  fn canonical_key(&self, key: &str) -> String {
+    if let Some(canonical) = self.aliases.get(key) {
+      return canonical.clone();
+    }
+    match self.key_case {
+      Some(key_case) => camel_case(key, key_case.separator()),
+      None => key.to_string(),
+    }
+  }
+
+  /// Pair every key of the input value with its canonical key, fails when keys map to the same key.
+  fn resolve_keys(&self, obj_keys: Vec<String>) -> Result<Vec<(String, String)>> {
+    let mut keys: Vec<(String, String)> = Vec::with_capacity(obj_keys.len());
+    for source in obj_keys {
+      let canonical = self.canonical_key(&source);
+      if let Some((_, other)) = keys.iter().find(|(k, _)| *k == canonical) {
         return Err(invalid_arg(
           format!(
-            "Unknown key(s) [{}] in strict object",
-            obj_keys.into_iter().collect::<Vec<String>>().join(", ")
+            "Keys '{}' and '{}' both map to key '{}'",
+            other, source, canonical
           )
           .as_str(),
         ));
       }
-
-      for key in obj_keys {
-        obj.delete_named_property(key.as_str())?;
-      }
+      keys.push((canonical, source));
     }
+    Ok(keys)
+  }
 
-    Ok(obj.into_unknown())
+  /// Whether checking has to look at keys which are not in the shape.
+  fn scans_unknown_keys(&self) -> bool {
+    self.strict
+      || self.catchall.is_some()
+      || !self.pattern_properties.is_empty()
+      || self.property_names.is_some()
+      || self.min_properties.is_some()
+      || self.max_properties.is_some()
   }
 
   // #[napi(ts_return_type = "R")]
@@ -125,9 +743,11 @@ impl BObject {
     &self,
     #[napi(ts_arg_type = "BObject<T>")] schema: ClassInstance<BObject>,
   ) -> BObject {
-    let others = schema.clone().inner;
+    let others = schema.clone();
     let mut clone = self.clone();
-    clone.inner.extend(others);
+    clone.inner.extend(others.inner);
+    clone.aliases.extend(others.aliases);
+    clone.rules.extend(others.rules);
     clone.base.optional = false;
     clone.base.nullable = false;
 
@@ -140,6 +760,19 @@ impl BObject {
   pub fn strict(&self) -> BObject {
     BObject {
       strict: true,
+      passthrough: false,
+      ..self.clone()
+    }
+  }
+
+  /// Keep unknown keys when parsing without validating their values, replaces the strict option.
+  #[napi(
+    ts_return_type = "BObject<(Exclude<R, null | undefined> & Record<string, unknown>) | Extract<R, null | undefined>>"
+  )]
+  pub fn passthrough(&self) -> BObject {
+    BObject {
+      strict: false,
+      passthrough: true,
       ..self.clone()
     }
   }
@@ -152,6 +785,383 @@ impl BObject {
       ..self.clone()
     }
   }
+
+  /// Keep unknown keys when parsing and validate their values with `schema`.
+  /// Takes precedence over the strict option.
+  #[napi(
+    ts_args_type = "schema: BValue<T>",
+    ts_return_type = "BObject<(Exclude<R, null | undefined> & Record<string, T>) | Extract<R, null | undefined>>"
+  )]
+  pub fn catchall(&self, env: Env, schema: JsObject) -> Result<BObject> {
+    Ok(BObject {
+      catchall: Some(Box::new(unwrap_schema(env, schema)?)),
+      ..self.clone()
+    })
+  }
+
+  /// Keep unknown keys matching a pattern when parsing and validate their values with its schema.
+  /// When a key matches multiple patterns it has to be valid for all of them.
+  #[napi(
+    ts_args_type = "patterns: Record<string, BValue>",
+    ts_return_type = "BObject<R>"
+  )]
+  pub fn pattern_properties(
+    &self,
+    env: Env,
+    patterns: HashMap<String, JsObject>,
+  ) -> Result<BObject> {
+    let mut clone = self.clone();
+    for (pattern, schema) in patterns {
+      clone
+        .pattern_properties
+        .push((Pattern::new(&pattern)?, unwrap_schema(env, schema)?));
+    }
+    Ok(clone)
+  }
+
+  /// Validate every key of the parsed object with a string schema.
+  #[napi(ts_args_type = "schema: BValue<string>", ts_return_type = "BObject<R>")]
+  pub fn property_names(&self, env: Env, schema: JsObject) -> Result<BObject> {
+    Ok(BObject {
+      property_names: Some(Box::new(unwrap_schema(env, schema)?)),
+      ..self.clone()
+    })
+  }
+
+  /// Minimum amount of keys of the parsed object, unknown keys which are removed are not counted.
+  #[napi(ts_return_type = "BObject<R>")]
+  pub fn min_properties(&self, value: u32) -> BObject {
+    BObject {
+      min_properties: Some(value),
+      ..self.clone()
+    }
+  }
+
+  /// Maximum amount of keys of the parsed object, unknown keys which are removed are not counted.
+  #[napi(ts_return_type = "BObject<R>")]
+  pub fn max_properties(&self, value: u32) -> BObject {
+    BObject {
+      max_properties: Some(value),
+      ..self.clone()
+    }
+  }
+
+  /// Only allow plain objects, created by an object literal or `Object.create(null)`.
+  /// Arrays, class instances and objects like Dates are rejected.
+  #[napi(ts_return_type = "BObject<R>")]
+  pub fn plain(&self) -> BObject {
+    BObject {
+      plain: true,
+      ..self.clone()
+    }
+  }
+
+  /// Reject arrays, which are otherwise accepted as objects with index keys.
+  #[napi(ts_return_type = "BObject<R>")]
+  pub fn reject_arrays(&self) -> BObject {
+    BObject {
+      reject_arrays: true,
+      ..self.clone()
+    }
+  }
+
+  /// Create a new object schema with `key` added or replaced.
+  /// With the `from` option the key is read from another key of the input value and renamed.
+  #[napi(
+    ts_args_type = "key: K, schema: BValue<T>, options?: KeyOptions",
+    ts_return_type = "BObject<(Omit<Exclude<R, null | undefined>, K> & { [key in K]: T }) | Extract<R, null | undefined>>"
+  )]
+  pub fn key(
+    &self,
+    env: Env,
+    key: String,
+    schema: JsObject,
+    options: Option<KeyOptions>,
+  ) -> Result<BObject> {
+    let mut clone = self.clone();
+    clone.aliases.retain(|_, canonical| *canonical != key);
+    if let Some(from) = options.and_then(|options| options.from) {
+      clone.aliases.insert(from, key.clone());
+    }
+    clone.inner.insert(key, unwrap_schema(env, schema)?);
+    Ok(clone)
+  }
+
+  /// Convert `snake_case` or `kebab-case` keys of the input value to camelCase when parsing.
+  /// Keys with an alias set by `key()` are renamed to their alias instead.
+  #[napi(
+    ts_args_type = "from: 'snake_case' | 'kebab-case'",
+    ts_return_type = "BObject<R>"
+  )]
+  pub fn camel_case_keys(&self, from: String) -> Result<BObject> {
+    let key_case = match from.as_str() {
+      "snake_case" => KeyCase::Snake,
+      "kebab-case" => KeyCase::Kebab,
+      _ => return Err(invalid_arg(format!("Unknown key case '{}'", from).as_str())),
+    };
+
+    Ok(BObject {
+      key_case: Some(key_case),
+      ..self.clone()
+    })
+  }
+
+  /// Require the values of 2 keys to be strictly equal, e.g. a password and its confirmation.
+  #[napi(
+    ts_args_type = "a: Extract<keyof Exclude<R, null | undefined>, string>, b: Extract<keyof Exclude<R, null | undefined>, string>",
+    ts_return_type = "BObject<R>"
+  )]
+  pub fn equal_fields(&self, a: String, b: String) -> BObject {
+    self.with_rule(ObjectRule::EqualFields(a, b))
+  }
+
+  /// Require the number or Date of key `a` to be less than the one of key `b`.
+  /// Passes when either key is missing.
+  #[napi(
+    ts_args_type = "a: Extract<keyof Exclude<R, null | undefined>, string>, b: Extract<keyof Exclude<R, null | undefined>, string>",
+    ts_return_type = "BObject<R>"
+  )]
+  pub fn less_than(&self, a: String, b: String) -> BObject {
+    self.with_rule(ObjectRule::LessThan(a, b))
+  }
+
+  /// Require `key` when all keys of `conditions` strictly equal their values.
+  #[napi(
+    ts_args_type = "key: Extract<keyof Exclude<R, null | undefined>, string>, conditions: Partial<Exclude<R, null | undefined>>",
+    ts_return_type = "BObject<R>"
+  )]
+  pub fn required_if(
+    &self,
+    env: Env,
+    key: String,
+    conditions: HashMap<String, JsUnknown>,
+  ) -> Result<BObject> {
+    Ok(self.with_rule(ObjectRule::required_if(env, key, conditions)?))
+  }
+
+  /// Allow at most one of the keys to be present, null values count as missing.
+  #[napi(
+    ts_args_type = "keys: Extract<keyof Exclude<R, null | undefined>, string>[]",
+    ts_return_type = "BObject<R>"
+  )]
+  pub fn mutually_exclusive(&self, keys: Vec<String>) -> BObject {
+    self.with_rule(ObjectRule::MutuallyExclusive(keys))
+  }
+
+  /// Require at least one of the keys to be present, null values count as missing.
+  #[napi(
+    ts_args_type = "keys: Extract<keyof Exclude<R, null | undefined>, string>[]",
+    ts_return_type = "BObject<R>"
+  )]
+  pub fn at_least_one_of(&self, keys: Vec<String>) -> BObject {
+    self.with_rule(ObjectRule::AtLeastOneOf(keys))
+  }
+
+  /// Freeze the parsed value with `Object.freeze`, nested objects and arrays as well when `deep`.
+  #[napi(
+    ts_args_type = "deep?: D",
+    ts_return_type = "BObject<(D extends true ? DeepReadonly<Exclude<R, null | undefined>> : Readonly<Exclude<R, null | undefined>>) | Extract<R, null | undefined>>"
+  )]
+  pub fn readonly(&self, deep: Option<bool>) -> Self {
+    BObject {
+      readonly: Some(match deep {
+        Some(true) => Freeze::Deep,
+        _ => Freeze::Shallow,
+      }),
+      ..self.clone()
+    }
+  }
+
+  /// Create a new object schema with only the given keys.
+  /// Optional and Nullable parameters of the object are ignored for new object.
+  #[napi(
+    ts_args_type = "keys: K[]",
+    ts_return_type = "BObject<Pick<Exclude<R, null | undefined>, K>>"
+  )]
+  pub fn pick(&self, keys: Vec<String>) -> BObject {
+    self.derive(|inner| inner.retain(|key, _| keys.contains(key)))
+  }
+
+  /// Create a new object schema without the given keys.
+  /// Optional and Nullable parameters of the object are ignored for new object.
+  #[napi(
+    ts_args_type = "keys: K[]",
+    ts_return_type = "BObject<Omit<Exclude<R, null | undefined>, K>>"
+  )]
+  pub fn omit(&self, keys: Vec<String>) -> BObject {
+    self.derive(|inner| inner.retain(|key, _| !keys.contains(key)))
+  }
+
+  /// Create a new object schema with the given keys, or all keys when omitted, made optional.
+  /// Optional and Nullable parameters of the object are ignored for new object.
+  #[napi(
+    ts_args_type = "keys?: K[]",
+    ts_return_type = "BObject<Omit<Exclude<R, null | undefined>, K> & Partial<Pick<Exclude<R, null | undefined>, K>>>"
+  )]
+  pub fn partial(&self, keys: Option<Vec<String>>) -> BObject {
+    self.derive(|inner| set_keys_optional(inner, keys.as_deref(), true))
+  }
+
+  /// Create a new object schema with all keys optional, including keys of nested objects.
+  /// Optional and Nullable parameters of the object are ignored for new object.
+  #[napi(ts_return_type = "BObject<DeepPartial<Exclude<R, null | undefined>>>")]
+  pub fn deep_partial(&self) -> BObject {
+    self.derive(|inner| {
+      for b_type in inner.values_mut() {
+        *b_type = deep_partial(b_type);
+        get_btype_base_mut(b_type).optional = true;
+      }
+    })
+  }
+
+  /// Create a new object schema with the given keys, or all keys when omitted, made required.
+  /// Optional and Nullable parameters of the object are ignored for new object.
+  #[napi(
+    ts_args_type = "keys?: K[]",
+    ts_return_type = "BObject<Omit<Exclude<R, null | undefined>, K> & Required<Pick<Exclude<R, null | undefined>, K>>>"
+  )]
+  pub fn required_keys(&self, keys: Option<Vec<String>>) -> BObject {
+    self.derive(|inner| set_keys_optional(inner, keys.as_deref(), false))
+  }
+
+  /// Create a new object schema with keys added, existing keys are overwritten.
+  /// Optional and Nullable parameters of the object are ignored for new object.
+  #[napi(
+    ts_args_type = "shape: T",
+    ts_return_type = "BObject<Omit<Exclude<R, null | undefined>, keyof T> & InferBObjectParseType<T>>"
+  )]
+  pub fn extend(&self, env: Env, shape: HashMap<String, JsObject>) -> Result<BObject> {
+    let mut extension = HashMap::with_capacity(shape.len());
+    for (key, schema) in shape {
+      extension.insert(key, unwrap_schema(env, schema)?);
+    }
+
+    Ok(self.derive(|inner| inner.extend(extension)))
+  }
+
+  /// Create an enum schema of the object keys.
+  #[napi(ts_return_type = "BEnum<Extract<keyof Exclude<R, null | undefined>, string>>")]
+  pub fn keyof(&self) -> BEnum {
+    let mut keys: Vec<String> = self.inner.keys().cloned().collect();
+    keys.sort();
+    BEnum::new(keys)
+  }
+
+  fn with_rule(&self, rule: ObjectRule) -> BObject {
+    let mut clone = self.clone();
+    clone.rules.push(rule);
+    clone
+  }
+
+  fn derive(&self, f: impl FnOnce(&mut HashMap<String, BType>)) -> BObject {
+    let mut clone = self.clone();
+    f(&mut clone.inner);
+    let inner = &clone.inner;
+    clone
+      .aliases
+      .retain(|_, canonical| inner.contains_key(canonical));
+    // rules of removed keys would fail for every value without them
+    clone.rules.retain(|rule| {
+      rule
+        .keys()
+        .iter()
+        .all(|key| inner.contains_key(*key) || !self.inner.contains_key(*key))
+    });
+    clone.base.optional = false;
+    clone.base.nullable = false;
+
+    clone
+  }
+}
+
+/// Parse the property `key` of `obj`, read from `source` when the key is renamed.
+/// The result is written back when it can differ from the input, async results to `key`
+/// as they resolve after the value was renamed.
+fn parse_property(
+  ctx: &mut ParseContext,
+  obj: &mut JsObject,
+  key: &str,
+  source: &str,
+  b_type: &BType,
+) -> Result<()> {
+  let property = obj.get_named_property_unchecked::<JsUnknown>(source)?;
+  let parsed = ctx
+    .nested(key, |ctx| parse_btype(b_type, property, ctx))
+    .map_err(|err| with_path(err, key))?;
+  if ctx.is_async() && parsed.is_promise()? {
+    let owned_key = key.to_string();
+    ctx.defer_write(obj, key, parsed, move |obj, value| {
+      Ok(obj.set_named_property(&owned_key, value)?)
+    })?;
+  } else if ctx.copies() || transforms_value(b_type) {
+    obj.set_named_property(source, parsed)?;
+  }
+
+  Ok(())
+}
+
+/// Whether the prototype of an object is `Object.prototype` or `null`.
+fn is_plain_object(env: Env, obj: &JsObject) -> Result<bool> {
+  let object: JsObject = env.get_global()?.get_named_property_unchecked("Object")?;
+  let prototype = object
+    .get_named_property_unchecked::<JsFunction>("getPrototypeOf")?
+    .call(None, &[obj])?;
+
+  match prototype.get_type()? {
+    ValueType::Null => Ok(true),
+    _ => Ok(env.strict_equals(
+      prototype,
+      object.get_named_property_unchecked::<JsUnknown>("prototype")?,
+    )?),
+  }
+}
+
+/// Convert a key with words split by `separator` to camelCase, leading separators are kept.
+fn camel_case(key: &str, separator: char) -> String {
+  let mut camel = String::with_capacity(key.len());
+  let mut upper = false;
+  for c in key.chars() {
+    if c == separator && !camel.trim_start_matches(separator).is_empty() {
+      upper = true;
+    } else if upper {
+      camel.extend(c.to_uppercase());
+      upper = false;
+    } else {
+      camel.push(c);
+    }
+  }
+  camel
+}
+
+fn set_keys_optional(inner: &mut HashMap<String, BType>, keys: Option<&[String]>, optional: bool) {
+  for (key, b_type) in inner.iter_mut() {
+    if keys.is_none_or(|keys| keys.contains(key)) {
+      get_btype_base_mut(b_type).optional = optional;
+    }
+  }
+}
+
+/// Make all keys of nested objects optional, recursing into arrays, tuples and unions.
+fn deep_partial(b_type: &BType) -> BType {
+  match b_type {
+    BType::Object(s) => BType::Object(BObject {
+      inner: s
+        .inner
+        .iter()
+        .map(|(key, b_type)| {
+          let mut b_type = deep_partial(b_type);
+          get_btype_base_mut(&mut b_type).optional = true;
+          (key.clone(), b_type)
+        })
+        .collect(),
+      ..s.clone()
+    }),
+    BType::Array(s) => BType::Array(s.map_inner(deep_partial)),
+    BType::Tuple(s) => BType::Tuple(s.map_inner(deep_partial)),
+    BType::Union(s) => BType::Union(s.map_inner(deep_partial)),
+    BType::Conditional(s) => BType::Conditional(s.map_inner(deep_partial)),
+    _ => b_type.clone(),
+  }
 }
 
 // pub struct Measure {
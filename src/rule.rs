@@ -0,0 +1,230 @@
+use napi::{Env, JsObject, JsString, JsUnknown, ValueType};
+
+use crate::{
+  common::{invalid_arg, with_path, PersistentValue},
+  definition::{missing, DefinitionReader},
+  error::{Error, Result},
+};
+
+/// Reads a property of the object a rule is evaluated on, missing keys are undefined.
+pub type GetProperty<'a> = dyn Fn(&str) -> Result<JsUnknown> + 'a;
+
+/// Constraint between fields of an object, evaluated after the fields are parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectRule {
+  EqualFields(String, String),
+  LessThan(String, String),
+  /// Key is required when all other keys have the given values.
+  RequiredIf {
+    key: String,
+    conditions: Vec<(String, PersistentValue)>,
+  },
+  MutuallyExclusive(Vec<String>),
+  AtLeastOneOf(Vec<String>),
+}
+
+impl ObjectRule {
+  pub fn required_if(
+    env: Env,
+    key: String,
+    conditions: impl IntoIterator<Item = (String, JsUnknown)>,
+  ) -> Result<Self> {
+    let conditions = conditions
+      .into_iter()
+      .map(|(key, value)| Ok((key, PersistentValue::new(env, value)?)))
+      .collect::<Result<Vec<_>>>()?;
+
+    Ok(ObjectRule::RequiredIf { key, conditions })
+  }
+
+  /// Keys of the object the rule reads.
+  pub fn keys(&self) -> Vec<&str> {
+    match self {
+      ObjectRule::EqualFields(a, b) | ObjectRule::LessThan(a, b) => vec![a, b],
+      ObjectRule::RequiredIf { key, conditions } => std::iter::once(key)
+        .chain(conditions.iter().map(|(key, _)| key))
+        .map(String::as_str)
+        .collect(),
+      ObjectRule::MutuallyExclusive(keys) | ObjectRule::AtLeastOneOf(keys) => {
+        keys.iter().map(String::as_str).collect()
+      }
+    }
+  }
+
+  pub fn check(&self, env: Env, get: &GetProperty) -> Result<bool> {
+    match self {
+      ObjectRule::EqualFields(a, b) => Ok(env.strict_equals(get(a)?, get(b)?)?),
+      ObjectRule::LessThan(a, b) => {
+        let (a, b) = (get(a)?, get(b)?);
+        if is_nullish(&a)? || is_nullish(&b)? {
+          return Ok(true);
+        }
+
+        match (comparable(a)?, comparable(b)?) {
+          (Some(a), Some(b)) => Ok(a < b),
+          _ => Ok(false),
+        }
+      }
+      ObjectRule::RequiredIf { key, conditions } => {
+        for (condition_key, value) in conditions {
+          if !env.strict_equals(get(condition_key)?, value.get::<JsUnknown>(env)?)? {
+            return Ok(true);
+          }
+        }
+        Ok(!is_nullish(&get(key)?)?)
+      }
+      ObjectRule::MutuallyExclusive(keys) => Ok(present_keys(keys, get)?.len() <= 1),
+      ObjectRule::AtLeastOneOf(keys) => Ok(!present_keys(keys, get)?.is_empty()),
+    }
+  }
+
+  /// Read a rule from the object returned by `definition()`.
+  pub fn from_definition(reader: &mut DefinitionReader) -> Result<Self> {
+    let rule_types = [
+      "equalFields",
+      "lessThan",
+      "requiredIf",
+      "mutuallyExclusive",
+      "atLeastOneOf",
+    ];
+    let type_name = reader
+      .choice("type", &rule_types)?
+      .ok_or_else(|| missing("type"))?;
+
+    Ok(match type_name {
+      "equalFields" => {
+        ObjectRule::EqualFields(reader.required_str("a")?, reader.required_str("b")?)
+      }
+      "lessThan" => ObjectRule::LessThan(reader.required_str("a")?, reader.required_str("b")?),
+      "requiredIf" => {
+        let key = reader.required_str("key")?;
+        let env = reader.env();
+        let when = reader.object("when")?.ok_or_else(|| missing("when"))?;
+        let names = when.get_property_names()?;
+        let conditions = (0..names.get_array_length()?)
+          .map(|i| {
+            let name = names
+              .get_element::<JsString>(i)?
+              .into_utf8()?
+              .into_owned()?;
+            let value = when.get_named_property::<JsUnknown>(&name)?;
+            Ok((name, value))
+          })
+          .collect::<Result<Vec<_>>>()?;
+        ObjectRule::required_if(env, key, conditions)?
+      }
+      _ => {
+        let keys = reader.strings("keys")?.ok_or_else(|| missing("keys"))?;
+        match type_name {
+          "mutuallyExclusive" => ObjectRule::MutuallyExclusive(keys),
+          _ => ObjectRule::AtLeastOneOf(keys),
+        }
+      }
+    })
+  }
+
+  /// Plain object describing the rule, used by `toDefinition()`.
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let type_name = match self {
+      ObjectRule::EqualFields(..) => "equalFields",
+      ObjectRule::LessThan(..) => "lessThan",
+      ObjectRule::RequiredIf { .. } => "requiredIf",
+      ObjectRule::MutuallyExclusive(_) => "mutuallyExclusive",
+      ObjectRule::AtLeastOneOf(_) => "atLeastOneOf",
+    };
+
+    let mut obj = env.create_object()?;
+    obj.set_named_property("type", env.create_string(type_name)?)?;
+    match self {
+      ObjectRule::EqualFields(a, b) | ObjectRule::LessThan(a, b) => {
+        obj.set_named_property("a", env.create_string(a)?)?;
+        obj.set_named_property("b", env.create_string(b)?)?;
+      }
+      ObjectRule::RequiredIf { key, conditions } => {
+        let mut when = env.create_object()?;
+        for (condition_key, value) in conditions {
+          when.set_named_property(condition_key, value.get::<JsUnknown>(env)?)?;
+        }
+        obj.set_named_property("key", env.create_string(key)?)?;
+        obj.set_named_property("when", when)?;
+      }
+      ObjectRule::MutuallyExclusive(keys) | ObjectRule::AtLeastOneOf(keys) => {
+        let mut array = env.create_array_with_length(keys.len())?;
+        for (i, key) in keys.iter().enumerate() {
+          array.set_element(i as u32, env.create_string(key)?)?;
+        }
+        obj.set_named_property("keys", array)?;
+      }
+    }
+    Ok(obj)
+  }
+
+  /// Build the error of a rule which did not pass `check`.
+  pub fn to_error(&self, get: &GetProperty) -> Result<Error> {
+    Ok(match self {
+      ObjectRule::EqualFields(a, b) => with_path(
+        invalid_arg(format!("Key '{}' does not equal key '{}'", b, a).as_str()),
+        b,
+      ),
+      ObjectRule::LessThan(a, b) => with_path(
+        invalid_arg(format!("Key '{}' is not less than key '{}'", a, b).as_str()),
+        b,
+      ),
+      ObjectRule::RequiredIf { key, conditions } => {
+        let condition_keys: Vec<&str> = conditions.iter().map(|(k, _)| k.as_str()).collect();
+        with_path(
+          invalid_arg(
+            format!(
+              "Key '{}' is required by the values of [{}]",
+              key,
+              condition_keys.join(", ")
+            )
+            .as_str(),
+          ),
+          key,
+        )
+      }
+      ObjectRule::MutuallyExclusive(keys) => {
+        let error =
+          invalid_arg(format!("Keys [{}] are mutually exclusive", keys.join(", ")).as_str());
+        match present_keys(keys, get)?.get(1) {
+          Some(key) => with_path(error, key),
+          None => error,
+        }
+      }
+      ObjectRule::AtLeastOneOf(keys) => invalid_arg(
+        format!(
+          "Object requires at least one of the keys [{}]",
+          keys.join(", ")
+        )
+        .as_str(),
+      ),
+    })
+  }
+}
+
+fn is_nullish(value: &JsUnknown) -> Result<bool> {
+  Ok(matches!(
+    value.get_type()?,
+    ValueType::Undefined | ValueType::Null
+  ))
+}
+
+fn present_keys<'a>(keys: &'a [String], get: &GetProperty) -> Result<Vec<&'a String>> {
+  let mut present = vec![];
+  for key in keys {
+    if !is_nullish(&get(key)?)? {
+      present.push(key);
+    }
+  }
+  Ok(present)
+}
+
+/// Numbers and Dates can be compared, Dates by their timestamp.
+fn comparable(value: JsUnknown) -> Result<Option<f64>> {
+  match value.get_type()? {
+    ValueType::Number => Ok(Some(value.coerce_to_number()?.get_double()?)),
+    ValueType::Object if value.is_date()? => Ok(Some(value.coerce_to_number()?.get_double()?)),
+    _ => Ok(None),
+  }
+}
@@ -1,6 +1,15 @@
-use napi::{JsString, JsUnknown};
+use napi::{Env, JsObject, JsString, JsUnknown, ValueType};
 
-use crate::{common::invalid_arg, impl_base_methods, impl_parse_safe_method, BValueBase};
+use crate::{
+  common::{invalid_arg, with_path, Pattern},
+  definition::{check_range, Definition, DefinitionReader},
+  error::Result,
+  format::StringFormat,
+  impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter,
+  jtd::JtdWriter,
+  BValueBase,
+};
 
 #[napi]
 #[derive(Debug, Clone, PartialEq)]
@@ -9,6 +18,8 @@ pub struct BString {
   len: Option<u32>,
   min: Option<u32>,
   max: Option<u32>,
+  pattern: Option<Pattern>,
+  format: Option<StringFormat>,
 }
 
 impl_base_methods!(
@@ -18,9 +29,11 @@ impl_base_methods!(
   "BString<R | null>",
   "BString<R | null | undefined>",
   "BString<Exclude<R, undefined>>",
-  "BString<Exclude<R, null>>"
+  "BString<Exclude<R, null>>",
+  "BString<T | Extract<R, null | undefined>>",
+  "BString<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method!(BString);
+impl_parse_methods!(BString);
 
 #[napi]
 impl BString {
@@ -31,11 +44,78 @@ impl BString {
       len: None,
       min: None,
       max: None,
+      pattern: None,
+      format: None,
     }
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, value: JsUnknown) -> napi::Result<JsUnknown> {
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "string", &self.base)?;
+    definition.set_u32("length", self.len)?;
+    definition.set_u32("min", self.min)?;
+    definition.set_u32("max", self.max)?;
+    definition.set_str("pattern", self.pattern.as_ref().map(Pattern::as_str))?;
+    definition.set_str("format", self.format.map(|format| format.name()))?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    let (min, max) = (reader.u32("min")?, reader.u32("max")?);
+    check_range("min", min, "max", max)?;
+    let pattern = reader
+      .str("pattern")?
+      .map(|pattern| Pattern::new(&pattern).map_err(|err| with_path(err, "pattern")))
+      .transpose()?;
+    let format = reader
+      .str("format")?
+      .map(|format| StringFormat::from_name(&format).map_err(|err| with_path(err, "format")))
+      .transpose()?;
+
+    Ok(BString {
+      base,
+      len: reader.u32("length")?,
+      min,
+      max,
+      pattern,
+      format,
+    })
+  }
+
+  pub fn jtd(&self, writer: &mut JtdWriter) -> Result<JsObject> {
+    if self.len.is_some() || self.min.is_some() || self.max.is_some() {
+      return Err(invalid_arg("String lengths can not be represented in JTD"));
+    }
+    if self.pattern.is_some() {
+      return Err(invalid_arg("String patterns can not be represented in JTD"));
+    }
+    match self.format {
+      Some(StringFormat::DateTime) => writer.typed("timestamp"),
+      Some(format) => Err(invalid_arg(
+        format!(
+          "The format '{}' can not be represented in JTD",
+          format.name()
+        )
+        .as_str(),
+      )),
+      None => writer.typed("string"),
+    }
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let mut obj = writer.typed("string")?;
+    writer.set_u32(&mut obj, "minLength", self.len.or(self.min))?;
+    writer.set_u32(&mut obj, "maxLength", self.len.or(self.max))?;
+    if let Some(pattern) = &self.pattern {
+      obj.set_named_property("pattern", writer.env().create_string(pattern.as_str())?)?;
+    }
+    if let Some(format) = self.format {
+      obj.set_named_property("format", writer.env().create_string(format.name())?)?;
+    }
+    Ok(obj)
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
@@ -44,7 +124,8 @@ impl BString {
     // TODO: unsure if using utf16 or utf8 is better, speeds differs based on input;
     let js_utf8 = js_string.into_utf8()?;
     let str = js_utf8.as_str()?;
-    let length = str.len() as u32;
+    // Lengths count code points, like `minLength` and `maxLength` of JSON Schema.
+    let length = str.chars().count() as u32;
 
     if let Some(expected) = self.len {
       if expected != length {
@@ -68,8 +149,51 @@ impl BString {
         }
       }
     }
+    if let Some(pattern) = &self.pattern {
+      if !pattern.is_match(str) {
+        return Err(invalid_arg(
+          format!("String does not match pattern '{}'", pattern.as_str()).as_str(),
+        ));
+      }
+    }
+    if let Some(format) = self.format {
+      if !format.is_valid(str) {
+        return Err(invalid_arg(
+          format!("String is not a valid {}", format.name()).as_str(),
+        ));
+      }
+    }
 
-    Ok(js_string.into_unknown())
+    self.base.apply_effects(ctx, js_string.into_unknown())
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    if value.get_type()? != ValueType::String {
+      return Ok(None);
+    }
+    let js_string = unsafe { value.cast::<JsString>() };
+    let js_utf8 = js_string.into_utf8()?;
+    let str = js_utf8.as_str()?;
+    let length = str.chars().count() as u32;
+
+    let valid = match self.len {
+      Some(expected) => expected == length,
+      None => self.min.is_none_or(|min| length >= min) && self.max.is_none_or(|max| length <= max),
+    } && self
+      .pattern
+      .as_ref()
+      .is_none_or(|pattern| pattern.is_match(str))
+      && self.format.is_none_or(|format| format.is_valid(str));
+    if !valid {
+      return Ok(None);
+    }
+
+    self.base.check_effects(env, js_string.into_unknown())
   }
 
   #[napi]
@@ -95,4 +219,25 @@ impl BString {
       ..self.clone()
     }
   }
+
+  /// Require the string to match a regular expression, which is not anchored by default.
+  #[napi(ts_return_type = "BString<R>")]
+  pub fn pattern(&self, pattern: String) -> Result<Self> {
+    Ok(BString {
+      pattern: Some(Pattern::new(&pattern)?),
+      ..self.clone()
+    })
+  }
+
+  /// Require the string to be in a named format, like the `format` keyword of JSON Schema.
+  #[napi(
+    ts_args_type = "format: 'date-time' | 'date' | 'time' | 'email' | 'hostname' | 'ipv4' | 'ipv6' | 'uri' | 'uuid'",
+    ts_return_type = "BString<R>"
+  )]
+  pub fn format(&self, format: String) -> Result<Self> {
+    Ok(BString {
+      format: Some(StringFormat::from_name(&format)?),
+      ..self.clone()
+    })
+  }
 }
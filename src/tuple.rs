@@ -1,8 +1,15 @@
-use napi::{bindgen_prelude::ClassInstance, Env, Error, JsObject, JsUnknown, Result, Status};
+use napi::{bindgen_prelude::ClassInstance, Env, JsObject, JsUnknown};
 
 use crate::{
-  common::{invalid_arg, parse_btype},
-  impl_base_methods, impl_parse_safe_method_with_env, BType, BValueBase, BWrapped,
+  common::{
+    check_btype, get_btype_base, invalid_arg, parse_btype, transforms_value, unwrap_schema,
+    with_path, ArrayOptions, Freeze,
+  },
+  definition::{Definition, DefinitionReader},
+  error::Result,
+  impl_base_methods, impl_parse_methods,
+  json_schema::{Draft, JsonSchemaWriter},
+  BType, BValueBase, BWrapped,
 };
 
 #[napi]
@@ -10,6 +17,12 @@ use crate::{
 pub struct BTuple {
   base: BValueBase,
   inner: Vec<BType>,
+  /// Type of the elements after the tuple elements.
+  rest: Option<Box<BType>>,
+  /// Names of the tuple elements used in error paths, empty when unnamed.
+  names: Vec<String>,
+  array: ArrayOptions,
+  readonly: Option<Freeze>,
 }
 
 impl_base_methods!(
@@ -19,9 +32,11 @@ impl_base_methods!(
   "BTuple<R | null>",
   "BTuple<R | null | undefined>",
   "BTuple<Exclude<R, undefined>>",
-  "BTuple<Exclude<R, null>>"
+  "BTuple<Exclude<R, null>>",
+  "BTuple<T | Extract<R, null | undefined>>",
+  "BTuple<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method_with_env!(BTuple);
+impl_parse_methods!(BTuple);
 
 #[napi]
 impl BTuple {
@@ -32,34 +47,265 @@ impl BTuple {
     BTuple {
       base: BValueBase::default(),
       inner,
+      rest: None,
+      names: vec![],
+      array: ArrayOptions::default(),
+      readonly: None,
     }
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, env: Env, value: JsUnknown) -> Result<JsUnknown> {
+  /// For internal use only!
+  #[napi(js_name = "_fromWrappedNamed", ts_return_type = "unknown")]
+  pub fn from_wrapped_named(
+    names: Vec<String>,
+    wrapped: Vec<ClassInstance<BWrapped>>,
+  ) -> Result<BTuple> {
+    if names.len() != wrapped.len() {
+      return Err(invalid_arg("Every tuple element needs a name"));
+    }
+
+    Ok(BTuple {
+      names,
+      ..BTuple::from_wrapped(wrapped)
+    })
+  }
+
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "tuple", &self.base)?;
+    definition.set_schemas("elements", self.inner.iter())?;
+    if !self.names.is_empty() {
+      definition.set_strings("names", &self.names)?;
+    }
+    definition.set_schema("rest", self.rest.as_deref())?;
+    definition.set_array_options(&self.array)?;
+    definition.set_readonly(self.readonly)?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    let inner = reader.schemas("elements")?.unwrap_or_default();
+    let names = reader.strings("names")?.unwrap_or_default();
+    if !names.is_empty() && names.len() != inner.len() {
+      return Err(with_path(
+        invalid_arg(format!("Expected {} names, got: {}", inner.len(), names.len()).as_str()),
+        "names",
+      ));
+    }
+
+    Ok(BTuple {
+      base,
+      inner,
+      rest: reader.schema("rest")?.map(Box::new),
+      names,
+      array: reader.array_options()?,
+      readonly: reader.readonly()?,
+    })
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let mut obj = writer.typed("array")?;
+    let elements = writer.schemas(self.inner.iter())?;
+    let rest = match self.rest.as_deref() {
+      Some(rest) => writer.schema(rest)?.into_unknown(),
+      None => writer.env().get_boolean(false)?.into_unknown(),
+    };
+    match writer.draft() {
+      Draft::Draft2020 => {
+        obj.set_named_property("prefixItems", elements)?;
+        obj.set_named_property("items", rest)?;
+      }
+      Draft::Draft07 => {
+        obj.set_named_property("items", elements)?;
+        obj.set_named_property("additionalItems", rest)?;
+      }
+    }
+
+    let (min_len, _) = self.length_range();
+    if min_len > 0 {
+      writer.set_u32(&mut obj, "minItems", Some(min_len))?;
+    }
+    Ok(obj)
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
+    let pending = ctx.pending_len();
 
-    let obj: JsObject = value.try_into()?;
+    let mut obj = ctx.writable(self.array.expect_array(ctx.env, value)?)?;
 
-    let expected_len = self.inner.len();
-    let val_len = obj.get_array_length()?;
-    if val_len != expected_len as u32 {
+    let val_len = obj.get_array_length_unchecked()?;
+    let val_len = self.array.fill_holes(ctx.env, &mut obj, val_len)?;
+    let (min_len, max_len) = self.length_range();
+    if Some(min_len) == max_len && val_len != min_len {
       return Err(invalid_arg(
         format!(
           "Tuple length ({}) does not equal expected length ({})",
-          val_len, expected_len
+          val_len, min_len
+        )
+        .as_str(),
+      ));
+    } else if val_len < min_len {
+      return Err(invalid_arg(
+        format!(
+          "Tuple length ({}) is lower than minimum length ({})",
+          val_len, min_len
         )
         .as_str(),
       ));
+    } else if let Some(max_len) = max_len.filter(|max_len| val_len > *max_len) {
+      return Err(invalid_arg(
+        format!(
+          "Tuple length ({}) is higher than maximum length ({})",
+          val_len, max_len
+        )
+        .as_str(),
+      ));
+    }
+
+    for i in 0..val_len {
+      let b_type = self.element_type(i);
+      let el = obj.get_element::<JsUnknown>(i)?;
+      let segment = self.segment(i);
+      let parsed = ctx.nested(&segment, |ctx| parse_btype(b_type, el, ctx));
+      let parsed = parsed.map_err(|err| with_path(err, &segment))?;
+      if ctx.is_async() && parsed.is_promise()? {
+        ctx.defer_write(&obj, &segment, parsed, move |obj, value| {
+          Ok(obj.set_element(i, value)?)
+        })?;
+      } else if ctx.copies() || transforms_value(b_type) {
+        obj.set_element(i, parsed)?;
+      }
+    }
+
+    let parsed = self.base.apply_effects(ctx, obj.into_unknown())?;
+    match self.readonly {
+      Some(freeze) => freeze.apply(ctx, pending, parsed),
+      None => Ok(parsed),
+    }
+  }
+
+  /// Minimum and maximum tuple length, trailing optional elements can be absent.
+  fn length_range(&self) -> (u32, Option<u32>) {
+    let required = self
+      .inner
+      .iter()
+      .rposition(|b_type| !get_btype_base(b_type).optional)
+      .map_or(0, |i| i + 1);
+    let max_len = match self.rest {
+      Some(_) => None,
+      None => Some(self.inner.len() as u32),
+    };
+    (required as u32, max_len)
+  }
+
+  /// Path segment of the element at index `i`, e.g. `1:price` for named tuples.
+  fn segment(&self, i: u32) -> String {
+    match self.names.get(i as usize) {
+      Some(name) => format!("{}:{}", i, name),
+      None => i.to_string(),
+    }
+  }
+
+  /// Type of the element at index `i`, which has to be within the length range.
+  fn element_type(&self, i: u32) -> &BType {
+    match (self.inner.get(i as usize), self.rest.as_deref()) {
+      (Some(b_type), _) | (None, Some(b_type)) => b_type,
+      (None, None) => unreachable!("tuple element index is checked against the maximum length"),
+    }
+  }
+
+  /// Freeze the parsed value with `Object.freeze`, nested objects and arrays as well when `deep`.
+  #[napi(
+    ts_args_type = "deep?: D",
+    ts_return_type = "BTuple<(D extends true ? DeepReadonly<Exclude<R, null | undefined>> : Readonly<Exclude<R, null | undefined>>) | Extract<R, null | undefined>>"
+  )]
+  pub fn readonly(&self, deep: Option<bool>) -> Self {
+    BTuple {
+      readonly: Some(match deep {
+        Some(true) => Freeze::Deep,
+        _ => Freeze::Shallow,
+      }),
+      ..self.clone()
+    }
+  }
+
+  /// Create a copy with every inner type mapped by `f`.
+  pub fn map_inner(&self, f: impl Fn(&BType) -> BType) -> BTuple {
+    BTuple {
+      inner: self.inner.iter().map(&f).collect(),
+      rest: self.rest.as_deref().map(|b_type| Box::new(f(b_type))),
+      ..self.clone()
+    }
+  }
+
+  /// Allow any amount of elements after the tuple elements, parsed with `schema`.
+  #[napi(
+    ts_args_type = "schema: BValue<T>",
+    ts_return_type = "BTuple<(Exclude<R, null | undefined> extends readonly unknown[] ? [...Exclude<R, null | undefined>, ...T[]] : never) | Extract<R, null | undefined>>"
+  )]
+  pub fn rest(&self, env: Env, schema: JsObject) -> Result<BTuple> {
+    Ok(BTuple {
+      rest: Some(Box::new(unwrap_schema(env, schema)?)),
+      ..self.clone()
+    })
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    let Some(obj) = self.array.as_array(env, value)? else {
+      return Ok(None);
+    };
+    let Some((obj, length)) = self.array.check_holes(env, obj)? else {
+      return Ok(None);
+    };
+    let (min_len, max_len) = self.length_range();
+    if length < min_len || max_len.is_some_and(|max_len| length > max_len) {
+      return Ok(None);
     }
 
-    for (i, b_type) in self.inner.iter().enumerate() {
-      let el = obj.get_element::<JsUnknown>(i as u32)?;
-      parse_btype(&b_type, el, env)?;
+    for i in 0..length {
+      let el = obj.get_element::<JsUnknown>(i)?;
+      if check_btype(self.element_type(i), el, env)?.is_none() {
+        return Ok(None);
+      }
     }
 
-    Ok(obj.into_unknown())
+    self.base.check_effects(env, obj.into_unknown())
+  }
+
+  /// How holes of sparse arrays are handled, by default they are parsed as undefined.
+  /// Compacting removes holes in place, moving later elements to lower indices.
+  #[napi(
+    ts_args_type = "policy: 'undefined' | 'reject' | 'compact'",
+    ts_return_type = "BTuple<R>"
+  )]
+  pub fn holes(&self, policy: String) -> Result<Self> {
+    Ok(BTuple {
+      array: self.array.with_holes(&policy)?,
+      ..self.clone()
+    })
+  }
+
+  /// Accept `arguments` objects and typed arrays, parsing converts them to arrays.
+  #[napi(ts_return_type = "BTuple<R>")]
+  pub fn array_like(&self) -> Self {
+    BTuple {
+      array: ArrayOptions {
+        array_like: true,
+        ..self.array
+      },
+      ..self.clone()
+    }
+  }
+
+  pub fn converts(&self) -> bool {
+    self.array.converts()
   }
 }
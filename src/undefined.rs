@@ -1,6 +1,9 @@
-use napi::{JsUndefined, JsUnknown, Result};
+use napi::{Env, JsObject, JsUndefined, JsUnknown, ValueType};
 
-use crate::{impl_base_methods, impl_parse_safe_method, BValueBase};
+use crate::{
+  definition::Definition, error::Result, impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter, BValueBase,
+};
 
 #[napi]
 #[derive(Debug, Clone, PartialEq)]
@@ -15,9 +18,11 @@ impl_base_methods!(
   "BUndefined<R | null>",
   "BUndefined<R | null | undefined>",
   "BUndefined<Exclude<R, undefined>>",
-  "BUndefined<Exclude<R, null>>"
+  "BUndefined<Exclude<R, null>>",
+  "BUndefined<T | Extract<R, null | undefined>>",
+  "BUndefined<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method!(BUndefined);
+impl_parse_methods!(BUndefined);
 
 #[napi]
 impl BUndefined {
@@ -26,18 +31,46 @@ impl BUndefined {
     BUndefined {
       base: BValueBase {
         optional: true,
-        nullable: false,
+        ..BValueBase::default()
       },
     }
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, value: JsUnknown) -> Result<JsUnknown> {
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let definition = Definition::new(env, "undefined", &self.base)?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(base: BValueBase) -> Self {
+    BUndefined { base }
+  }
+
+  /// JSON has no undefined, the schema matches no value.
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let mut obj = writer.env().create_object()?;
+    obj.set_named_property("not", writer.env().create_object()?)?;
+    Ok(obj)
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
 
     let js_undefined: JsUndefined = value.try_into()?;
-    Ok(js_undefined.into_unknown())
+    self.base.apply_effects(ctx, js_undefined.into_unknown())
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    match value.get_type()? {
+      ValueType::Undefined => self.base.check_effects(env, value),
+      _ => Ok(None),
+    }
   }
 }
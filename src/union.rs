@@ -1,8 +1,17 @@
-use napi::{bindgen_prelude::ClassInstance, Env, Error, JsUnknown, Result, Status};
+use napi::{bindgen_prelude::ClassInstance, Env, JsObject, JsUnknown, Status};
 
 use crate::{
-  common::{invalid_arg, parse_btype, ClonableJsUnknown},
-  impl_base_methods, impl_parse_safe_method_with_env, BType, BValueBase, BWrapped,
+  common::{
+    check_btype, get_btype_base, invalid_arg, parse_btype, transforms_value, with_path,
+    ClonableJsUnknown, PersistentValue,
+  },
+  context::{is_invalid, rethrow, then_catch},
+  definition::{Definition, DefinitionReader},
+  error::{Error, Result},
+  impl_base_methods, impl_parse_methods,
+  json_schema::JsonSchemaWriter,
+  jtd::JtdWriter,
+  BType, BValueBase, BWrapped,
 };
 
 #[napi]
@@ -10,6 +19,8 @@ use crate::{
 pub struct BUnion {
   base: BValueBase,
   inner: Vec<BType>,
+  /// Values have to match exactly one of the types.
+  exclusive: bool,
 }
 
 impl_base_methods!(
@@ -19,9 +30,11 @@ impl_base_methods!(
   "BUnion<R | null>",
   "BUnion<R | null | undefined>",
   "BUnion<Exclude<R, undefined>>",
-  "BUnion<Exclude<R, null>>"
+  "BUnion<Exclude<R, null>>",
+  "BUnion<T | Extract<R, null | undefined>>",
+  "BUnion<(Exclude<R, null | undefined> & { __brand: B }) | Extract<R, null | undefined>>"
 );
-impl_parse_safe_method_with_env!(BUnion);
+impl_parse_methods!(BUnion);
 
 #[napi]
 impl BUnion {
@@ -32,11 +45,129 @@ impl BUnion {
     BUnion {
       base: BValueBase::default(),
       inner,
+      exclusive: false,
     }
   }
 
-  #[napi(ts_return_type = "R")]
-  pub fn parse(&self, env: Env, value: JsUnknown) -> Result<JsUnknown> {
+  pub fn definition(&self, env: Env) -> Result<JsObject> {
+    let mut definition = Definition::new(env, "union", &self.base)?;
+    definition.set_schemas("options", self.inner.iter())?;
+    definition.set_flag("exclusive", self.exclusive)?;
+    Ok(definition.into_object())
+  }
+
+  pub fn from_definition(reader: &mut DefinitionReader, base: BValueBase) -> Result<Self> {
+    let inner = reader.schemas("options")?.unwrap_or_default();
+    if inner.is_empty() {
+      return Err(with_path(invalid_arg("Union has no options"), "options"));
+    }
+    Ok(BUnion {
+      base,
+      inner,
+      exclusive: reader.flag("exclusive")?,
+    })
+  }
+
+  pub fn json_schema(&self, writer: &mut JsonSchemaWriter) -> Result<JsObject> {
+    let mut obj = writer.env().create_object()?;
+    let keyword = match self.exclusive {
+      true => "oneOf",
+      false => "anyOf",
+    };
+    let options = writer
+      .schemas(self.inner.iter())
+      .map_err(|err| with_path(err, keyword))?;
+    obj.set_named_property(keyword, options)?;
+
+    if let (true, Some(key)) = (writer.components(), self.discriminator()) {
+      let env = writer.env();
+      let mut discriminator = env.create_object()?;
+      discriminator.set_named_property("propertyName", env.create_string(&key)?)?;
+      // the mapping can only point to named components
+      let mut mapping = env.create_object()?;
+      let mut mapped = false;
+      for b_type in &self.inner {
+        if let (BType::Object(s), Some(reference)) = (b_type, writer.component_ref(b_type)) {
+          for (_, values) in s.tags().into_iter().filter(|(tag, _)| **tag == key) {
+            for value in values {
+              mapping.set_named_property(&value, env.create_string(&reference)?)?;
+              mapped = true;
+            }
+          }
+        }
+      }
+      if mapped {
+        discriminator.set_named_property("mapping", mapping)?;
+      }
+      obj.set_named_property("discriminator", discriminator)?;
+    }
+    Ok(obj)
+  }
+
+  pub fn jtd(&self, writer: &mut JtdWriter) -> Result<JsObject> {
+    let Some(key) = self.discriminator() else {
+      return Err(invalid_arg(
+        "Unions can only be represented in JTD when the options are objects tagged by a key",
+      ));
+    };
+
+    let env = writer.env();
+    let mut mapping = env.create_object()?;
+    for (i, b_type) in self.inner.iter().enumerate() {
+      let BType::Object(s) = b_type else {
+        continue;
+      };
+      let base = get_btype_base(b_type);
+      if base.nullable {
+        return Err(with_path(
+          invalid_arg("Nullable options of tagged unions can not be represented in JTD"),
+          &i.to_string(),
+        ));
+      }
+      for (_, values) in s.tags().into_iter().filter(|(tag, _)| **tag == key) {
+        for value in values {
+          let mut properties = s
+            .jtd_properties(writer, Some(&key))
+            .map_err(|err| with_path(err, &i.to_string()))?;
+          writer.set_metadata(&mut properties, base)?;
+          mapping.set_named_property(&value, properties)?;
+        }
+      }
+    }
+
+    let mut obj = env.create_object()?;
+    obj.set_named_property("discriminator", env.create_string(&key)?)?;
+    obj.set_named_property("mapping", mapping)?;
+    Ok(obj)
+  }
+
+  /// Key telling the options apart when all options are objects with distinct values for it.
+  fn discriminator(&self) -> Option<String> {
+    let tags = self
+      .inner
+      .iter()
+      .map(|b_type| match b_type {
+        BType::Object(s) => Some(s.tags()),
+        _ => None,
+      })
+      .collect::<Option<Vec<_>>>()?;
+
+    let (first, others) = tags.split_first()?;
+    first.iter().map(|(key, _)| *key).find_map(|key| {
+      let mut seen: Vec<&String> = vec![];
+      for option in &tags {
+        let (_, values) = option.iter().find(|(tag, _)| *tag == key)?;
+        if values.iter().any(|value| seen.contains(&value)) {
+          return None;
+        }
+        seen.extend(values);
+      }
+      (!others.is_empty()).then(|| key.clone())
+    })
+  }
+
+  pub fn parse_with(&self, ctx: &mut ParseContext, value: JsUnknown) -> Result<JsUnknown> {
+    let value = self.base.preprocess(ctx.env, value)?;
     if self.base.skip_parse(&value)? {
       return Ok(value);
     }
@@ -52,23 +183,144 @@ impl BUnion {
     // }
 
     // TODO: this seems like the best way for now to clone JsValues
-    let clonable = ClonableJsUnknown::new(env, value)?;
+    let clonable = ClonableJsUnknown::new(ctx.env, value)?;
+    self.parse_from(ctx, &clonable, 0)
+  }
 
-    for b_type in &self.inner {
-      match parse_btype(&b_type, clonable.create_clone()?, env) {
-        Ok(r) => return Ok(r),
+  /// Parse with the first type from index `start` on the value is valid for.
+  /// Async steps of a type are awaited before it matches, when they fail the next type is tried.
+  fn parse_from(
+    &self,
+    ctx: &mut ParseContext,
+    value: &ClonableJsUnknown,
+    start: usize,
+  ) -> Result<JsUnknown> {
+    for (i, b_type) in self.inner.iter().enumerate().skip(start) {
+      let mut member = ctx.fork_member();
+      match parse_btype(b_type, value.create_clone()?, &mut member) {
+        Ok(r) => {
+          if member.pending_len() > 0 || (member.is_async() && r.is_promise()?) {
+            return self.settle_member(ctx, member, value, i, r);
+          }
+          ctx.merge(member);
+          return self.matched(ctx, value, i, r);
+        }
         // invalid value for BType, continue to next type to check
         Err(Error {
           status: Status::InvalidArg,
           ..
-        }) => continue,
-        Err(err) => return Err(err),
+        }) => member.discard()?,
+        Err(err) => {
+          member.discard()?;
+          return Err(err);
+        }
       };
     }
 
     Err(invalid_arg("Value did not match any of the Union types"))
   }
 
+  fn matched(
+    &self,
+    ctx: &mut ParseContext,
+    value: &ClonableJsUnknown,
+    i: usize,
+    parsed: JsUnknown,
+  ) -> Result<JsUnknown> {
+    if self.exclusive && self.matches_other(ctx.env, value, i)? {
+      return Err(invalid_arg(
+        "Value matched more than one of the exclusive Union types",
+      ));
+    }
+    self.base.apply_effects(ctx, parsed)
+  }
+
+  /// Promise for the value parsed by the type at index `i` once its async steps resolved,
+  /// or for the value parsed by the next matching type when they failed.
+  fn settle_member(
+    &self,
+    ctx: &mut ParseContext,
+    member: ParseContext,
+    value: &ClonableJsUnknown,
+    i: usize,
+    parsed: JsUnknown,
+  ) -> Result<JsUnknown> {
+    let env = ctx.env;
+    let settled = member.settle_member(ctx, parsed)?;
+    let value = PersistentValue::new(env, value.create_clone()?)?;
+
+    let (union, fork, fulfilled_value) = (self.clone(), ctx.fork(), value.clone());
+    let on_fulfilled = move |env, parsed| {
+      let value = ClonableJsUnknown::new(env, fulfilled_value.get(env)?)?;
+      let mut ctx = fork.fork();
+      let result = union.matched(&mut ctx, &value, i, parsed);
+      Ok(ctx.settle(result)?.into_unknown())
+    };
+
+    let (union, fork) = (self.clone(), ctx.fork());
+    let on_rejected = move |env, reason| {
+      if !is_invalid(&reason)? {
+        return rethrow(env, reason);
+      }
+      let value = ClonableJsUnknown::new(env, value.get(env)?)?;
+      let mut ctx = fork.fork();
+      let result = union.parse_from(&mut ctx, &value, i + 1);
+      Ok(ctx.settle(result)?.into_unknown())
+    };
+
+    then_catch(env, settled, on_fulfilled, on_rejected).map(|promise| promise.into_unknown())
+  }
+
+  /// Create a copy with every inner type mapped by `f`.
+  pub fn map_inner(&self, f: impl Fn(&BType) -> BType) -> BUnion {
+    BUnion {
+      inner: self.inner.iter().map(f).collect(),
+      ..self.clone()
+    }
+  }
+
+  pub fn check_with(&self, env: Env, value: JsUnknown) -> Result<Option<JsUnknown>> {
+    let value = self.base.preprocess(env, value)?;
+    if let Some(valid) = self.base.skip_check(&value)? {
+      return Ok(valid.then_some(value));
+    }
+
+    let clonable = ClonableJsUnknown::new(env, value)?;
+    for (i, b_type) in self.inner.iter().enumerate() {
+      if let Some(r) = check_btype(b_type, clonable.create_clone()?, env)? {
+        if self.exclusive && self.matches_other(env, &clonable, i)? {
+          return Ok(None);
+        }
+        return self.base.check_effects(env, r);
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Whether a type after the matched type at index `matched` accepts the value as well.
+  fn matches_other(&self, env: Env, value: &ClonableJsUnknown, matched: usize) -> Result<bool> {
+    for b_type in &self.inner[matched + 1..] {
+      if check_btype(b_type, value.create_clone()?, env)?.is_some() {
+        return Ok(true);
+      }
+    }
+    Ok(false)
+  }
+
+  /// Require values to match exactly one of the types, like `oneOf` of JSON Schema.
+  #[napi(ts_return_type = "BUnion<R>")]
+  pub fn exclusive(&self) -> BUnion {
+    BUnion {
+      exclusive: true,
+      ..self.clone()
+    }
+  }
+
+  pub fn inner_transforms(&self) -> bool {
+    self.inner.iter().any(transforms_value)
+  }
+
   #[napi(ts_return_type = "BUnion<R | T>")]
   pub fn merge(&self, #[napi(ts_arg_type = "BUnion<T>")] schema: ClassInstance<BUnion>) -> BUnion {
     let mut others = schema.clone().inner;